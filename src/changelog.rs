@@ -1,36 +1,116 @@
 use anyhow::{Context, Result};
 use git2::{Repository, Commit};
+use indexmap::IndexMap;
+use semver::Version;
 use std::fs;
+use std::path::Path;
+use crate::config::Config;
 
 pub struct ChangelogManager {
     repo: Repository,
+    config: Config,
+}
+
+/// Commit bullets grouped by conventional-commit type, in first-seen order,
+/// with a trailing "Other" bucket for commits that don't match the
+/// conventional format.
+#[derive(Default)]
+pub struct ChangelogSections {
+    sections: IndexMap<String, Vec<String>>,
+    other: Vec<String>,
+    breaking: Vec<String>,
+}
+
+impl ChangelogSections {
+    pub fn is_empty(&self) -> bool {
+        self.sections.is_empty() && self.other.is_empty() && self.breaking.is_empty()
+    }
+
+    pub fn has_breaking_changes(&self) -> bool {
+        !self.breaking.is_empty()
+    }
+
+    /// Render as Markdown: a dedicated breaking-changes block first (if any),
+    /// then one `##` heading per non-empty section in first-seen order,
+    /// followed by "Other" if present.
+    pub fn render(&self) -> String {
+        let mut rendered = String::new();
+
+        if !self.breaking.is_empty() {
+            rendered.push_str("## ⚠️ BREAKING CHANGES\n\n");
+            for bullet in &self.breaking {
+                rendered.push_str(bullet);
+                rendered.push('\n');
+            }
+            rendered.push('\n');
+        }
+
+        for (heading, bullets) in &self.sections {
+            if bullets.is_empty() {
+                continue;
+            }
+            rendered.push_str(&format!("## {}\n\n", heading));
+            for bullet in bullets {
+                rendered.push_str(bullet);
+                rendered.push('\n');
+            }
+            rendered.push('\n');
+        }
+
+        if !self.other.is_empty() {
+            rendered.push_str("## Other\n\n");
+            for bullet in &self.other {
+                rendered.push_str(bullet);
+                rendered.push('\n');
+            }
+            rendered.push('\n');
+        }
+
+        rendered.trim_end().to_string()
+    }
 }
 
 impl ChangelogManager {
-    pub fn new() -> Result<Self> {
+    pub fn new(config: Config) -> Result<Self> {
         let repo = Repository::open(".")
             .context("Failed to open git repository")?;
-        Ok(ChangelogManager { repo })
+        Ok(ChangelogManager { repo, config })
     }
 
-    pub fn generate_changes(&self, from_version: Option<&str>) -> Result<String> {
-        let mut changes = String::new();
+    /// Generate the categorized changes since the previous release.
+    ///
+    /// When `since_tag` is true, the previous release boundary is discovered
+    /// from the most recent semver `v*` tag reachable from HEAD (like `git
+    /// describe --tags --abbrev=0`), independent of what the project files
+    /// say. Otherwise the existing `from_version` behavior is used.
+    pub fn generate_changes(&self, from_version: Option<&str>, since_tag: bool) -> Result<ChangelogSections> {
+        let mut sections = ChangelogSections::default();
         let head = self.repo.head()?.peel_to_commit()?;
 
-        let from_commit = match from_version {
-            Some(version) => {
-                let tag_name = format!("v{}", version);
-                match self.repo.find_reference(&format!("refs/tags/{}", tag_name)) {
-                    Ok(reference) => reference.peel_to_commit()?,
-                    Err(_) => {
-                        println!("Warning: Previous version tag v{} not found, showing all commits", version);
-                        self.get_first_commit()?
+        let from_commit = if since_tag {
+            match self.find_latest_tag_commit(&head)? {
+                Some(commit) => commit,
+                None => {
+                    println!("Warning: no previous semver tag found reachable from HEAD, showing all commits");
+                    self.get_first_commit()?
+                }
+            }
+        } else {
+            match from_version {
+                Some(version) => {
+                    let tag_name = format!("v{}", version);
+                    match self.repo.find_reference(&format!("refs/tags/{}", tag_name)) {
+                        Ok(reference) => reference.peel_to_commit()?,
+                        Err(_) => {
+                            println!("Warning: Previous version tag v{} not found, showing all commits", version);
+                            self.get_first_commit()?
+                        }
                     }
+                },
+                None => {
+                    println!("No previous version specified, showing all commits");
+                    self.get_first_commit()?
                 }
-            },
-            None => {
-                println!("No previous version specified, showing all commits");
-                self.get_first_commit()?
             }
         };
 
@@ -43,38 +123,68 @@ impl ChangelogManager {
             let message = commit.message().unwrap_or("").trim();
             
             // Skip merge commits and version bump commits
-            if message.starts_with("Merge") || message.contains("bump version") {
+            if self.config.should_ignore_commit(message) {
                 continue;
             }
 
-            // Format the commit message
-            if let Some(formatted) = self.format_commit_message(message) {
-                changes.push_str(&formatted);
-                changes.push('\n');
+            // Bucket the commit into its conventional-commit section
+            let short_hash = &commit.id().to_string()[..7];
+            self.bucket_commit_message(message, short_hash, &mut sections);
+        }
+
+        Ok(sections)
+    }
+
+    pub fn update_changelog(&self, version: &str, changes: &ChangelogSections) -> Result<()> {
+        let changelog_path = &self.config.changelog_path;
+
+        // Create the changelog's parent directory if it doesn't exist
+        if let Some(parent) = Path::new(changelog_path).parent() {
+            if !parent.as_os_str().is_empty() {
+                std::fs::create_dir_all(parent)
+                    .with_context(|| format!("Failed to create {} directory", parent.display()))?;
             }
         }
 
-        Ok(changes)
+        let updated_content = self.render_changelog(version, changes)?;
+        fs::write(changelog_path, updated_content)?;
+        Ok(())
     }
 
-    pub fn update_changelog(&self, version: &str, changes: &str) -> Result<()> {
-        let changelog_dir = "docs";
-        let changelog_path = format!("{}/CHANGELOG.md", changelog_dir);
+    /// Compute the changelog file's content with `version`'s entry inserted,
+    /// without writing it. Used for both `update_changelog` and the
+    /// `--dry-run` / confirmation previews.
+    ///
+    /// Follows Keep a Changelog conventions: the new entry is a `## [version]
+    /// - date` heading. If the file has an `## [Unreleased]` marker, the
+    /// entry is inserted directly below it; otherwise it's inserted after the
+    /// top-level `# Changelog` header.
+    pub fn render_changelog(&self, version: &str, changes: &ChangelogSections) -> Result<String> {
+        let changelog_path = &self.config.changelog_path;
         let date = chrono::Local::now().format("%Y-%m-%d");
-        
-        // Create docs directory if it doesn't exist
-        std::fs::create_dir_all(changelog_dir)
-            .context("Failed to create docs directory")?;
-        
-        let new_content = format!(
-            "# {version} ({date})\n\n{changes}\n",
+
+        let new_entry = format!(
+            "## [{version}] - {date}\n\n{}\n",
+            changes.render(),
         );
 
-        let existing_content = fs::read_to_string(&changelog_path)
-            .unwrap_or_default();
+        let existing_content = fs::read_to_string(changelog_path).unwrap_or_default();
 
         let updated_content = if existing_content.is_empty() {
-            format!("# Changelog\n\n{new_content}")
+            format!("# Changelog\n\n{new_entry}")
+        } else if let Some(marker_pos) = existing_content.find("## [Unreleased]") {
+            let after_marker = marker_pos + "## [Unreleased]".len();
+            let insert_at = existing_content[after_marker..]
+                .find('\n')
+                .map(|i| after_marker + i + 1)
+                .unwrap_or(existing_content.len());
+
+            format!(
+                "{}\n{}{}",
+                &existing_content[..insert_at],
+                new_entry,
+                &existing_content[insert_at..],
+            )
         } else {
             // Find the position after the "# Changelog" header
             let pos = existing_content
@@ -86,48 +196,113 @@ impl ChangelogManager {
                 "{}{}{}",
                 &existing_content[..pos],
                 "\n\n",
-                new_content,
+                &format!("{}\n{}", new_entry, existing_content[pos..].trim_start()),
             )
         };
 
-        fs::write(&changelog_path, updated_content)?;
-        Ok(())
+        Ok(updated_content)
     }
 
-    fn format_commit_message(&self, message: &str) -> Option<String> {
+    fn bucket_commit_message(&self, message: &str, short_hash: &str, sections: &mut ChangelogSections) {
         // Skip empty messages
         if message.is_empty() {
-            return None;
+            return;
         }
 
         // Extract the first line
-        let first_line = message.lines().next()?;
-        
-        // Format based on conventional commits
-        if let Some(captures) = conventional_commit_regex().captures(first_line) {
-            let type_ = captures.get(1)?.as_str();
-            let scope = captures.get(2).map(|m| m.as_str());
-            let description = captures.get(3)?.as_str();
-
-            let formatted = match (type_, scope) {
-                ("feat", Some(scope)) => format!("- ✨ **{}:** {}", scope, description),
-                ("feat", None) => format!("- ✨ {}", description),
-                ("fix", Some(scope)) => format!("- 🐛 **{}:** {}", scope, description),
-                ("fix", None) => format!("- 🐛 {}", description),
-                ("docs", _) => format!("- 📚 {}", description),
-                ("style", _) => format!("- 💎 {}", description),
-                ("refactor", _) => format!("- ♻️ {}", description),
-                ("perf", _) => format!("- ⚡️ {}", description),
-                ("test", _) => format!("- ✅ {}", description),
-                ("build", _) => format!("- 📦 {}", description),
-                ("ci", _) => format!("- 👷 {}", description),
-                ("chore", _) => format!("- 🔧 {}", description),
-                _ => format!("- {}", first_line),
+        let Some(first_line) = message.lines().next() else {
+            return;
+        };
+
+        // A `BREAKING CHANGE:` footer can appear anywhere in the body. A
+        // commit can flag itself as breaking both this way and with a `!`
+        // subject marker; only one bullet should end up in the changelog,
+        // so track whether the footer already added one before the `!`
+        // marker below gets a chance to add a second.
+        let mut breaking_bullet_added = false;
+        for line in message.lines() {
+            if let Some(footer) = line.strip_prefix("BREAKING CHANGE:") {
+                sections.breaking.push(format!("- {} ({})", footer.trim(), short_hash));
+                breaking_bullet_added = true;
+            }
+        }
+
+        // Bucket based on conventional commits
+        match conventional_commit_regex().captures(first_line) {
+            Some(captures) => {
+                let type_ = &captures[1];
+                let scope = captures.get(2).map(|m| m.as_str());
+                let is_breaking = captures.get(3).is_some();
+                let Some(description) = captures.get(4).map(|m| m.as_str()) else {
+                    sections.other.push(format!("- {} ({})", first_line, short_hash));
+                    return;
+                };
+
+                if is_breaking && !breaking_bullet_added {
+                    let breaking_bullet = match scope {
+                        Some(scope) => format!("- **{}:** {} ({})", scope, description, short_hash),
+                        None => format!("- {} ({})", description, short_hash),
+                    };
+                    sections.breaking.push(breaking_bullet);
+                }
+
+                match self.config.commit_types.get(type_) {
+                    Some(commit_type) => {
+                        let prefix = if commit_type.emoji.is_empty() {
+                            "-".to_string()
+                        } else {
+                            format!("- {}", commit_type.emoji)
+                        };
+                        let bullet = match scope {
+                            Some(scope) => format!("{} **{}:** {} ({})", prefix, scope, description, short_hash),
+                            None => format!("{} {} ({})", prefix, description, short_hash),
+                        };
+                        sections
+                            .sections
+                            .entry(commit_type.heading.clone())
+                            .or_default()
+                            .push(bullet);
+                    }
+                    None => sections.other.push(format!("- {} ({})", first_line, short_hash)),
+                }
+            }
+            None => sections.other.push(format!("- {} ({})", first_line, short_hash)),
+        }
+    }
+
+    /// Find the highest semver `v*` tag that is a strict ancestor of `head`,
+    /// walking `refs/tags` directly rather than requiring the caller to
+    /// already know the previous version. A tag pointing at `head` itself is
+    /// excluded, so the "since last release" boundary is always below HEAD.
+    fn find_latest_tag_commit(&self, head: &Commit) -> Result<Option<Commit>> {
+        let mut best: Option<(Version, git2::Oid)> = None;
+
+        for name in self.repo.tag_names(None)?.iter().flatten() {
+            let version_str = name.strip_prefix('v').unwrap_or(name);
+            let Ok(version) = Version::parse(version_str) else {
+                continue;
             };
-            Some(formatted)
-        } else {
-            Some(format!("- {}", first_line))
+
+            let reference = self.repo.find_reference(&format!("refs/tags/{}", name))?;
+            let commit = reference.peel_to_commit()?;
+
+            // Strictly below HEAD: a tag pointing at HEAD itself isn't a
+            // valid "previous release" boundary, or `generate_changes` would
+            // diff HEAD against itself and report an empty changelog.
+            let reachable = commit.id() != head.id()
+                && self.repo.graph_descendant_of(head.id(), commit.id()).unwrap_or(false);
+            if !reachable {
+                continue;
+            }
+
+            if best.as_ref().map(|(best_version, _)| version > *best_version).unwrap_or(true) {
+                best = Some((version, commit.id()));
+            }
         }
+
+        best.map(|(_, oid)| self.repo.find_commit(oid))
+            .transpose()
+            .map_err(Into::into)
     }
 
     fn get_first_commit(&self) -> Result<Commit> {
@@ -145,5 +320,200 @@ impl ChangelogManager {
 }
 
 fn conventional_commit_regex() -> regex::Regex {
-    regex::Regex::new(r"^(\w+)(?:\(([^)]+)\))?: (.+)").unwrap()
+    regex::Regex::new(r"^(\w+)(?:\(([^)]+)\))?(!)?: (.+)").unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+    use tempfile::tempdir;
+
+    fn test_manager(config: Config) -> ChangelogManager {
+        let dir = tempdir().unwrap();
+        let repo = Repository::init(dir.path()).unwrap();
+        std::mem::forget(dir); // keep the temp dir alive for the manager's lifetime
+        ChangelogManager { repo, config }
+    }
+
+    fn commit_all(repo: &Repository, root: &Path, filename: &str, content: &str, message: &str) {
+        std::fs::write(root.join(filename), content).unwrap();
+        let mut index = repo.index().unwrap();
+        index.add_path(Path::new(filename)).unwrap();
+        index.write().unwrap();
+        let tree_id = index.write_tree().unwrap();
+        let tree = repo.find_tree(tree_id).unwrap();
+        let signature = git2::Signature::now("Test User", "test@example.com").unwrap();
+        let parent = repo.head().ok().and_then(|head| head.peel_to_commit().ok());
+        let parents: Vec<&Commit> = parent.iter().collect();
+        repo.commit(Some("HEAD"), &signature, &signature, message, &tree, &parents)
+            .unwrap();
+    }
+
+    #[test]
+    fn render_sections_orders_breaking_then_sections_then_other() {
+        let mut sections = ChangelogSections::default();
+        sections.breaking.push("- remove old config (abc1234)".to_string());
+        sections.sections.entry("Features".to_string()).or_default().push("- add widget (def5678)".to_string());
+        sections.other.push("- misc tweak (ghi9012)".to_string());
+
+        let rendered = sections.render();
+        let breaking_pos = rendered.find("BREAKING CHANGES").unwrap();
+        let features_pos = rendered.find("Features").unwrap();
+        let other_pos = rendered.find("Other").unwrap();
+        assert!(breaking_pos < features_pos);
+        assert!(features_pos < other_pos);
+    }
+
+    #[test]
+    fn render_sections_skips_empty_sections() {
+        let mut sections = ChangelogSections::default();
+        sections.sections.entry("Features".to_string()).or_default();
+        sections.sections.entry("Bug Fixes".to_string()).or_default().push("- fix thing (abc1234)".to_string());
+
+        let rendered = sections.render();
+        assert!(!rendered.contains("Features"));
+        assert!(rendered.contains("Bug Fixes"));
+    }
+
+    #[test]
+    fn bucket_commit_message_groups_by_configured_type() {
+        let manager = test_manager(Config::default());
+        let mut sections = ChangelogSections::default();
+
+        manager.bucket_commit_message("feat: add a widget", "abc1234", &mut sections);
+        manager.bucket_commit_message("fix: correct a typo", "def5678", &mut sections);
+
+        assert_eq!(sections.sections["Features"], vec!["- ✨ add a widget (abc1234)"]);
+        assert_eq!(sections.sections["Bug Fixes"], vec!["- 🐛 correct a typo (def5678)"]);
+    }
+
+    #[test]
+    fn bucket_commit_message_with_scope_bolds_the_scope() {
+        let manager = test_manager(Config::default());
+        let mut sections = ChangelogSections::default();
+
+        manager.bucket_commit_message("feat(api): add an endpoint", "abc1234", &mut sections);
+
+        assert_eq!(sections.sections["Features"], vec!["- ✨ **api:** add an endpoint (abc1234)"]);
+    }
+
+    #[test]
+    fn bucket_commit_message_unconventional_goes_to_other() {
+        let manager = test_manager(Config::default());
+        let mut sections = ChangelogSections::default();
+
+        manager.bucket_commit_message("just a plain commit message", "abc1234", &mut sections);
+
+        assert_eq!(sections.other, vec!["- just a plain commit message (abc1234)"]);
+    }
+
+    #[test]
+    fn bucket_commit_message_unrecognized_type_goes_to_other() {
+        let manager = test_manager(Config::default());
+        let mut sections = ChangelogSections::default();
+
+        manager.bucket_commit_message("wip: update deps", "abc1234", &mut sections);
+
+        assert_eq!(sections.other, vec!["- wip: update deps (abc1234)"]);
+    }
+
+    #[test]
+    fn bucket_commit_message_bang_marker_is_breaking() {
+        let manager = test_manager(Config::default());
+        let mut sections = ChangelogSections::default();
+
+        manager.bucket_commit_message("feat!: rework the public API", "abc1234", &mut sections);
+
+        assert_eq!(sections.breaking, vec!["- rework the public API (abc1234)"]);
+        assert_eq!(sections.sections["Features"], vec!["- ✨ rework the public API (abc1234)"]);
+    }
+
+    #[test]
+    fn bucket_commit_message_breaking_change_footer_is_breaking() {
+        let manager = test_manager(Config::default());
+        let mut sections = ChangelogSections::default();
+
+        manager.bucket_commit_message(
+            "fix: correct a typo\n\nBREAKING CHANGE: removes the old config format",
+            "abc1234",
+            &mut sections,
+        );
+
+        assert_eq!(sections.breaking, vec!["- removes the old config format (abc1234)"]);
+    }
+
+    #[test]
+    fn bucket_commit_message_dedupes_bang_marker_and_footer() {
+        let manager = test_manager(Config::default());
+        let mut sections = ChangelogSections::default();
+
+        manager.bucket_commit_message(
+            "feat!: rework the public API\n\nBREAKING CHANGE: the old API is gone",
+            "abc1234",
+            &mut sections,
+        );
+
+        assert_eq!(sections.breaking.len(), 1);
+        assert_eq!(sections.breaking, vec!["- the old API is gone (abc1234)"]);
+    }
+
+    #[test]
+    fn generate_changes_buckets_commits_since_the_given_version_with_short_hashes() {
+        let dir = tempdir().unwrap();
+        let repo = Repository::init(dir.path()).unwrap();
+        commit_all(&repo, dir.path(), "a.txt", "1", "chore: initial commit");
+
+        {
+            let tagged_commit = repo.head().unwrap().peel_to_commit().unwrap();
+            let signature = git2::Signature::now("Test User", "test@example.com").unwrap();
+            repo.tag("v1.0.0", tagged_commit.as_object(), &signature, "release", false)
+                .unwrap();
+        }
+
+        commit_all(&repo, dir.path(), "a.txt", "2", "feat: add a widget");
+        let expected_hash = repo.head().unwrap().peel_to_commit().unwrap().id().to_string()[..7].to_string();
+
+        let manager = ChangelogManager { repo, config: Config::default() };
+        let changes = manager.generate_changes(Some("1.0.0"), false).unwrap();
+
+        assert_eq!(changes.sections["Features"], vec![format!("- ✨ add a widget ({})", expected_hash)]);
+    }
+
+    #[test]
+    fn render_changelog_creates_new_file_with_header() {
+        let dir = tempdir().unwrap();
+        let changelog_path = dir.path().join("CHANGELOG.md");
+        let mut config = Config::default();
+        config.changelog_path = changelog_path.to_string_lossy().into_owned();
+        let manager = test_manager(config);
+
+        let mut sections = ChangelogSections::default();
+        sections.other.push("- initial commit (abc1234)".to_string());
+
+        let rendered = manager.render_changelog("1.0.0", &sections).unwrap();
+        assert!(rendered.starts_with("# Changelog"));
+        assert!(rendered.contains("## [1.0.0]"));
+        assert!(rendered.contains("- initial commit (abc1234)"));
+    }
+
+    #[test]
+    fn render_changelog_inserts_under_unreleased_marker() {
+        let dir = tempdir().unwrap();
+        let changelog_path = dir.path().join("CHANGELOG.md");
+        std::fs::write(&changelog_path, "# Changelog\n\n## [Unreleased]\n\n## [0.9.0] - 2024-01-01\n\n- old stuff\n").unwrap();
+        let mut config = Config::default();
+        config.changelog_path = changelog_path.to_string_lossy().into_owned();
+        let manager = test_manager(config);
+
+        let mut sections = ChangelogSections::default();
+        sections.other.push("- new stuff (abc1234)".to_string());
+
+        let rendered = manager.render_changelog("1.0.0", &sections).unwrap();
+        let unreleased_pos = rendered.find("## [Unreleased]").unwrap();
+        let new_version_pos = rendered.find("## [1.0.0]").unwrap();
+        let old_version_pos = rendered.find("## [0.9.0]").unwrap();
+        assert!(unreleased_pos < new_version_pos);
+        assert!(new_version_pos < old_version_pos);
+    }
 } 
\ No newline at end of file
@@ -0,0 +1,146 @@
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+use crate::project_files::{ProjectFile, ProjectType};
+
+/// Project configuration loaded from `.bump.toml`, mirroring how `clog`
+/// supports `.clog.toml`. Every field falls back to the tool's built-in
+/// defaults when the file is absent or a key is omitted.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    /// Path the changelog is written to, relative to the repo root.
+    pub changelog_path: String,
+    /// Commit-type -> heading/emoji mapping used when formatting changelog entries.
+    pub commit_types: BTreeMap<String, CommitTypeConfig>,
+    /// Commit message prefixes to skip when walking history.
+    pub ignore_prefixes: Vec<String>,
+    /// Additional project files to keep in sync, beyond what auto-detection finds.
+    pub project_files: Vec<ConfiguredProjectFile>,
+    /// Per-project-type `version_format` overrides applied to auto-detected
+    /// files, e.g. `{ platformio = "v${raw}" }`. Files declared explicitly
+    /// under `[[project_files]]` use their own `version_format` instead.
+    pub version_formats: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct CommitTypeConfig {
+    /// Heading shown in the changelog, e.g. "Features".
+    pub heading: String,
+    /// Emoji prefix, e.g. "✨". Leave empty to omit.
+    #[serde(default)]
+    pub emoji: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ConfiguredProjectFile {
+    pub path: String,
+    pub project_type: String,
+    /// Required when `project_type = "custom"`: a regex with one capture
+    /// group wrapping the version token to replace.
+    #[serde(default)]
+    pub regex: Option<String>,
+    /// Template controlling how the version is written to this file, e.g.
+    /// `v${raw}`. See `ProjectFile::version_format`. Defaults to the bare
+    /// semver when omitted.
+    #[serde(default)]
+    pub version_format: Option<String>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            changelog_path: "docs/CHANGELOG.md".to_string(),
+            commit_types: default_commit_types(),
+            ignore_prefixes: vec!["Merge".to_string(), "bump version".to_string()],
+            project_files: Vec::new(),
+            version_formats: BTreeMap::new(),
+        }
+    }
+}
+
+impl Config {
+    /// Load `.bump.toml` from `project_root`, falling back to defaults when
+    /// the file does not exist.
+    pub fn load(project_root: &Path) -> Result<Self> {
+        let config_path = project_root.join(".bump.toml");
+        if !config_path.exists() {
+            return Ok(Config::default());
+        }
+
+        let content = fs::read_to_string(&config_path)
+            .with_context(|| format!("Failed to read {}", config_path.display()))?;
+        toml_edit::de::from_str(&content)
+            .with_context(|| format!("Failed to parse {}", config_path.display()))
+    }
+
+    /// Resolve the configured additional project files into `ProjectFile`s.
+    pub fn configured_project_files(&self) -> Vec<ProjectFile> {
+        self.project_files
+            .iter()
+            .filter_map(|configured| {
+                let project_type = match configured.project_type.as_str() {
+                    "rust" => ProjectType::Rust,
+                    "python" => ProjectType::Python,
+                    "cpp" => ProjectType::Cpp,
+                    "meson" => ProjectType::Meson,
+                    "platformio" => ProjectType::PlatformIO,
+                    "node" => ProjectType::Node,
+                    "maven" => ProjectType::Maven,
+                    "gradle" => ProjectType::Gradle,
+                    "go" => ProjectType::Go,
+                    "custom" => ProjectType::Custom(configured.regex.clone()?),
+                    _ => return None,
+                };
+                Some(
+                    ProjectFile::new(configured.path.clone(), project_type)
+                        .with_version_format(configured.version_format.clone()),
+                )
+            })
+            .collect()
+    }
+
+    /// The `version_format` override configured for `project_type`'s
+    /// `.bump.toml` key, if any, applied to auto-detected files.
+    pub fn version_format_for(&self, project_type: &str) -> Option<String> {
+        self.version_formats.get(project_type).cloned()
+    }
+
+    pub fn should_ignore_commit(&self, message: &str) -> bool {
+        let first_line = message.lines().next().unwrap_or(message);
+        self.ignore_prefixes
+            .iter()
+            .any(|prefix| first_line.starts_with(prefix.as_str()))
+    }
+}
+
+fn default_commit_types() -> BTreeMap<String, CommitTypeConfig> {
+    let defaults = [
+        ("feat", "✨", "Features"),
+        ("fix", "🐛", "Bug Fixes"),
+        ("docs", "📚", "Documentation"),
+        ("style", "💎", "Style"),
+        ("refactor", "♻️", "Refactoring"),
+        ("perf", "⚡️", "Performance"),
+        ("test", "✅", "Tests"),
+        ("build", "📦", "Build"),
+        ("ci", "👷", "CI"),
+        ("chore", "🔧", "Chores"),
+    ];
+
+    defaults
+        .into_iter()
+        .map(|(type_, emoji, heading)| {
+            (
+                type_.to_string(),
+                CommitTypeConfig {
+                    heading: heading.to_string(),
+                    emoji: emoji.to_string(),
+                },
+            )
+        })
+        .collect()
+}
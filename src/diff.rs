@@ -0,0 +1,23 @@
+use similar::{ChangeTag, TextDiff};
+
+/// Print a unified, line-level diff of `old` -> `new` under `label`, prefixing
+/// added/removed lines with `+`/`-` like a standard patch. Used by the
+/// confirmation preview and `--dry-run` to show exactly what a file's
+/// rendered content will change to before anything is written.
+pub fn print_file_diff(label: &str, old: &str, new: &str) {
+    if old == new {
+        println!("{label}: unchanged");
+        return;
+    }
+
+    println!("{label}:");
+    let diff = TextDiff::from_lines(old, new);
+    for change in diff.iter_all_changes() {
+        let sign = match change.tag() {
+            ChangeTag::Delete => "-",
+            ChangeTag::Insert => "+",
+            ChangeTag::Equal => " ",
+        };
+        print!("{sign} {change}");
+    }
+}
@@ -1,5 +1,26 @@
 use anyhow::{Context, Result};
-use git2::Repository;
+use git2::{Commit, Repository};
+use regex::Regex;
+use semver::Version;
+
+/// The SemVer level implied by the Conventional Commits since the last
+/// release, as computed by `GitManager::suggest_bump_level`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum SuggestedBump {
+    Major,
+    Minor,
+    Patch,
+}
+
+impl SuggestedBump {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SuggestedBump::Major => "major",
+            SuggestedBump::Minor => "minor",
+            SuggestedBump::Patch => "patch",
+        }
+    }
+}
 
 pub struct GitManager {
     repo: Repository,
@@ -58,6 +79,104 @@ impl GitManager {
         Ok(())
     }
 
+    /// Delete the local `v<version>` tag, e.g. after a failed release needs
+    /// to be rolled back.
+    pub fn delete_tag(&self, version: &str) -> Result<()> {
+        let tag_name = format!("v{}", version);
+        self.repo
+            .tag_delete(&tag_name)
+            .with_context(|| format!("Failed to delete local tag {}", tag_name))
+    }
+
+    /// Reset HEAD back to the parent of the release commit, undoing
+    /// `commit_version_bump` without touching the working directory (file
+    /// contents are restored separately via `VersionManager::revert`).
+    pub fn reset_release_commit(&self) -> Result<()> {
+        let head = self.repo.head()?;
+        let commit = self.repo.find_commit(head.target().unwrap())?;
+        let parent = commit
+            .parent(0)
+            .context("Release commit has no parent to reset to")?;
+
+        self.repo
+            .reset(parent.as_object(), git2::ResetType::Mixed, None)
+            .context("Failed to reset release commit")
+    }
+
+    /// Infer the SemVer bump level implied by the Conventional Commits
+    /// reachable from `HEAD` back to the most recent `v*` tag: any
+    /// `BREAKING CHANGE`/`!` marker implies major, any `feat` implies minor,
+    /// everything else implies patch. When no prior tag exists, the whole
+    /// history is scanned.
+    pub fn suggest_bump_level(&self) -> Result<SuggestedBump> {
+        let head = self.repo.head()?.peel_to_commit()?;
+        let from_commit = self.find_latest_release_tag_commit(&head)?;
+
+        let mut revwalk = self.repo.revwalk()?;
+        revwalk.push(head.id())?;
+        if let Some(commit) = &from_commit {
+            revwalk.hide(commit.id())?;
+        }
+
+        let commit_regex = conventional_commit_prefix_regex();
+        let mut level = SuggestedBump::Patch;
+
+        for oid in revwalk {
+            let commit = self.repo.find_commit(oid?)?;
+            let message = commit.message().unwrap_or("").trim().to_string();
+            if message.is_empty() {
+                continue;
+            }
+
+            if message.lines().any(|line| line.starts_with("BREAKING CHANGE:")) {
+                return Ok(SuggestedBump::Major);
+            }
+
+            let first_line = message.lines().next().unwrap_or("");
+            if let Some(caps) = commit_regex.captures(first_line) {
+                if caps.get(3).is_some() {
+                    return Ok(SuggestedBump::Major);
+                }
+                if &caps[1] == "feat" {
+                    level = SuggestedBump::Minor;
+                }
+            }
+        }
+
+        Ok(level)
+    }
+
+    /// Find the highest semver `v*` tag that is an ancestor of (or equal to)
+    /// `head`. Unlike `ChangelogManager::find_latest_tag_commit`, a tag
+    /// pointing at `head` itself is accepted.
+    fn find_latest_release_tag_commit(&self, head: &Commit) -> Result<Option<Commit>> {
+        let mut best: Option<(Version, git2::Oid)> = None;
+
+        for name in self.repo.tag_names(None)?.iter().flatten() {
+            let version_str = name.strip_prefix('v').unwrap_or(name);
+            let Ok(version) = Version::parse(version_str) else {
+                continue;
+            };
+
+            let reference = self.repo.find_reference(&format!("refs/tags/{}", name))?;
+            let commit = reference.peel_to_commit()?;
+
+            let reachable = commit.id() == head.id()
+                || self.repo.graph_descendant_of(head.id(), commit.id()).unwrap_or(false);
+            if !reachable {
+                continue;
+            }
+
+            if best.as_ref().map(|(best_version, _)| version > *best_version).unwrap_or(true) {
+                best = Some((version, commit.id()));
+            }
+        }
+
+        best.map(|(_, oid)| self.repo.find_commit(oid))
+            .transpose()
+            .map_err(Into::into)
+    }
+
     pub fn push_changes(&self, version: &str) -> Result<()> {
         println!("Pushing changes to remote...");
         
@@ -79,15 +198,75 @@ impl GitManager {
 
     pub fn create_github_release(&self, version: &str, changelog: &str) -> Result<()> {
         let tag_name = format!("v{}", version);
-        
+
         println!("Creating GitHub release for tag {}", tag_name);
-        
-        // Create GitHub release using gh CLI with output capture
+
+        match std::env::var("GITHUB_TOKEN").or_else(|_| std::env::var("GH_TOKEN")) {
+            Ok(token) => self.create_github_release_via_api(&tag_name, changelog, &token),
+            Err(_) => {
+                println!("No GITHUB_TOKEN/GH_TOKEN found; falling back to the gh CLI");
+                self.create_github_release_via_cli(&tag_name, changelog)
+            }
+        }
+    }
+
+    /// Create the release via the GitHub REST API, so the release flow
+    /// works on machines without the `gh` CLI installed (e.g. bare CI
+    /// containers).
+    fn create_github_release_via_api(&self, tag_name: &str, changelog: &str, token: &str) -> Result<()> {
+        let (owner, repo) = self.origin_owner_and_repo()?;
+
+        let url = format!("https://api.github.com/repos/{}/{}/releases", owner, repo);
+        let body = serde_json::json!({
+            "tag_name": tag_name,
+            "name": format!("Release {}", tag_name),
+            "body": changelog,
+            "make_latest": "true",
+        });
+
+        let client = reqwest::blocking::Client::new();
+        let response = client
+            .post(&url)
+            .header("Authorization", format!("Bearer {}", token))
+            .header("Accept", "application/vnd.github+json")
+            .header("User-Agent", "bump")
+            .json(&body)
+            .send()
+            .context("Failed to call GitHub releases API")?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let text = response.text().unwrap_or_default();
+            anyhow::bail!("GitHub release creation failed ({}): {}", status, text);
+        }
+
+        println!("Created GitHub release via REST API");
+        Ok(())
+    }
+
+    /// Parse the `owner/repo` pair out of the `origin` remote URL, supporting
+    /// both `https://github.com/owner/repo.git` and `git@github.com:owner/repo.git`.
+    fn origin_owner_and_repo(&self) -> Result<(String, String)> {
+        let remote = self
+            .repo
+            .find_remote("origin")
+            .context("Failed to find 'origin' remote")?;
+        let url = remote
+            .url()
+            .context("'origin' remote has no URL")?
+            .to_string();
+
+        parse_owner_repo(&url)
+    }
+
+    /// Create the release by shelling out to the `gh` CLI. Used when no
+    /// `GITHUB_TOKEN`/`GH_TOKEN` is available.
+    fn create_github_release_via_cli(&self, tag_name: &str, changelog: &str) -> Result<()> {
         let output = std::process::Command::new("gh")
             .args([
                 "release",
                 "create",
-                &tag_name,
+                tag_name,
                 "--title",
                 &format!("Release {}", tag_name),
                 "--notes",
@@ -109,4 +288,135 @@ impl GitManager {
         println!("Release output: {}", String::from_utf8_lossy(&output.stdout));
         Ok(())
     }
+}
+
+fn conventional_commit_prefix_regex() -> Regex {
+    Regex::new(r"^(\w+)(?:\(([^)]+)\))?(!)?:").unwrap()
+}
+
+// Parse `owner/repo` out of a `github.com` remote URL, supporting both
+// `https://github.com/owner/repo.git` and `git@github.com:owner/repo.git`.
+// The two forms are told apart by splitting on `github.com:`/`github.com/`
+// explicitly, rather than on the first colon, since an `https://` URL has a
+// scheme colon that comes before `github.com`.
+fn parse_owner_repo(url: &str) -> Result<(String, String)> {
+    let trimmed = url.trim_end_matches(".git").trim_end_matches('/');
+    let path = trimmed
+        .rsplit_once("github.com:")
+        .or_else(|| trimmed.rsplit_once("github.com/"))
+        .map(|(_, rest)| rest)
+        .with_context(|| format!("Could not parse owner/repo from origin URL: {}", url))?;
+
+    let (owner, repo) = path
+        .rsplit_once('/')
+        .with_context(|| format!("Could not parse owner/repo from origin URL: {}", url))?;
+
+    Ok((owner.to_string(), repo.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+    use tempfile::tempdir;
+
+    #[test]
+    fn parses_https_origin() {
+        let (owner, repo) = parse_owner_repo("https://github.com/owner/repo.git").unwrap();
+        assert_eq!(owner, "owner");
+        assert_eq!(repo, "repo");
+    }
+
+    #[test]
+    fn parses_ssh_origin() {
+        let (owner, repo) = parse_owner_repo("git@github.com:owner/repo.git").unwrap();
+        assert_eq!(owner, "owner");
+        assert_eq!(repo, "repo");
+    }
+
+    // Write `content` to `filename` and commit it, so each test can build up
+    // a small history of real commits without shelling out to `git`.
+    fn commit_all(repo: &Repository, root: &Path, filename: &str, content: &str, message: &str) {
+        std::fs::write(root.join(filename), content).unwrap();
+        let mut index = repo.index().unwrap();
+        index.add_path(Path::new(filename)).unwrap();
+        index.write().unwrap();
+        let tree_id = index.write_tree().unwrap();
+        let tree = repo.find_tree(tree_id).unwrap();
+        let signature = git2::Signature::now("Test User", "test@example.com").unwrap();
+        let parent = repo.head().ok().and_then(|head| head.peel_to_commit().ok());
+        let parents: Vec<&Commit> = parent.iter().collect();
+        repo.commit(Some("HEAD"), &signature, &signature, message, &tree, &parents)
+            .unwrap();
+    }
+
+    #[test]
+    fn suggest_bump_level_is_patch_by_default() {
+        let dir = tempdir().unwrap();
+        let repo = Repository::init(dir.path()).unwrap();
+        commit_all(&repo, dir.path(), "a.txt", "1", "chore: initial commit");
+        commit_all(&repo, dir.path(), "a.txt", "2", "fix: correct a typo");
+
+        let git_manager = GitManager { repo };
+        assert_eq!(git_manager.suggest_bump_level().unwrap(), SuggestedBump::Patch);
+    }
+
+    #[test]
+    fn suggest_bump_level_is_minor_for_feat() {
+        let dir = tempdir().unwrap();
+        let repo = Repository::init(dir.path()).unwrap();
+        commit_all(&repo, dir.path(), "a.txt", "1", "chore: initial commit");
+        commit_all(&repo, dir.path(), "a.txt", "2", "feat: add a new widget");
+        commit_all(&repo, dir.path(), "a.txt", "3", "fix: correct a typo");
+
+        let git_manager = GitManager { repo };
+        assert_eq!(git_manager.suggest_bump_level().unwrap(), SuggestedBump::Minor);
+    }
+
+    #[test]
+    fn suggest_bump_level_is_major_for_bang_marker() {
+        let dir = tempdir().unwrap();
+        let repo = Repository::init(dir.path()).unwrap();
+        commit_all(&repo, dir.path(), "a.txt", "1", "chore: initial commit");
+        commit_all(&repo, dir.path(), "a.txt", "2", "feat!: rework the public API");
+
+        let git_manager = GitManager { repo };
+        assert_eq!(git_manager.suggest_bump_level().unwrap(), SuggestedBump::Major);
+    }
+
+    #[test]
+    fn suggest_bump_level_is_major_for_breaking_change_footer() {
+        let dir = tempdir().unwrap();
+        let repo = Repository::init(dir.path()).unwrap();
+        commit_all(&repo, dir.path(), "a.txt", "1", "chore: initial commit");
+        commit_all(
+            &repo,
+            dir.path(),
+            "a.txt",
+            "2",
+            "fix: correct a typo\n\nBREAKING CHANGE: removes the old config format",
+        );
+
+        let git_manager = GitManager { repo };
+        assert_eq!(git_manager.suggest_bump_level().unwrap(), SuggestedBump::Major);
+    }
+
+    #[test]
+    fn suggest_bump_level_ignores_commits_before_the_latest_tag() {
+        let dir = tempdir().unwrap();
+        let repo = Repository::init(dir.path()).unwrap();
+        commit_all(&repo, dir.path(), "a.txt", "1", "feat!: an old breaking change");
+
+        {
+            let tagged_commit = repo.head().unwrap().peel_to_commit().unwrap();
+            let signature = git2::Signature::now("Test User", "test@example.com").unwrap();
+            repo.tag("v1.0.0", tagged_commit.as_object(), &signature, "release", false)
+                .unwrap();
+        }
+
+        commit_all(&repo, dir.path(), "a.txt", "2", "fix: correct a typo");
+
+        let git_manager = GitManager { repo };
+        assert_eq!(git_manager.suggest_bump_level().unwrap(), SuggestedBump::Patch);
+    }
 } 
\ No newline at end of file
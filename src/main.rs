@@ -1,13 +1,18 @@
 use clap::{Parser, ValueEnum};
 use anyhow::Result;
+use std::fs;
 use std::path::Path;
 mod project_files;
 mod version;
-use version::VersionManager;
-use crate::git::GitManager;
+use version::{PrereleaseChannel, VersionManager};
+use crate::git::{GitManager, SuggestedBump};
 mod git;
 use crate::changelog::ChangelogManager;
 mod changelog;
+use crate::config::Config;
+mod config;
+use crate::diff::print_file_diff;
+mod diff;
 
 #[derive(Parser)]
 #[command(author, version, about, long_about = None)]
@@ -23,6 +28,29 @@ struct Cli {
     /// Create a GitHub release
     #[arg(long)]
     release: bool,
+
+    /// Pre-release identifier to use when bumping or starting a pre-release
+    #[arg(long, default_value = "rc")]
+    pre_release_id: String,
+
+    /// Discover the previous release from the most recent git tag instead of
+    /// the version recorded in project files
+    #[arg(long)]
+    since_tag: bool,
+
+    /// Preview the version bump and changelog update without writing,
+    /// committing, or tagging anything
+    #[arg(long)]
+    dry_run: bool,
+
+    /// Attach `+<metadata>` build metadata to the bumped version
+    #[arg(long)]
+    build_metadata: Option<String>,
+
+    /// Fail instead of auto-reconciling when project files disagree on the
+    /// current version
+    #[arg(long)]
+    strict: bool,
 }
 
 #[derive(Copy, Clone, PartialEq, Eq, ValueEnum)]
@@ -31,55 +59,179 @@ enum BumpType {
     Minor,
     Patch,
     PreRelease,
+    /// Strip the pre-release suffix, promoting the current version to a final release
+    Finalize,
+    /// Infer major/minor/patch from the Conventional Commits since the last tag
+    Auto,
 }
 
 fn main() -> Result<()> {
     let cli = Cli::parse();
+    let config = Config::load(Path::new("."))?;
     let mut version_manager = VersionManager::new();
     let git_manager = GitManager::new()?;
-    let changelog_manager = ChangelogManager::new()?;
-    
+    let changelog_manager = ChangelogManager::new(config.clone())?;
+
     version_manager.detect_version_files(Path::new("."))?;
+    version_manager.apply_config(&config);
+
+    if let Err(drift) = version_manager.check_consistency() {
+        if cli.strict {
+            anyhow::bail!("{drift}");
+        }
+        println!("Warning: {drift}Reconciling onto the highest detected version.");
+        version_manager.reconcile_to_highest_version();
+    }
+
     let current_version = version_manager.get_current_version().to_string();
-    
-    let new_version = match cli.bump_type {
-        BumpType::Major => version_manager.bump_major(),
-        BumpType::Minor => version_manager.bump_minor(),
-        BumpType::Patch => version_manager.bump_patch(),
-        BumpType::PreRelease => version_manager.bump_patch(), // TODO: Implement pre-release
+
+    match cli.bump_type {
+        BumpType::Major => { version_manager.bump_major(); }
+        BumpType::Minor => { version_manager.bump_minor(); }
+        BumpType::Patch => { version_manager.bump_patch(); }
+        BumpType::PreRelease => {
+            // Recognized channel names (alpha/beta/rc) always take the
+            // ordered-channel path (no moving backwards, shared revision
+            // counter); `bump_prerelease` only runs for a custom label that
+            // isn't one of the three. Both paths start a fresh pre-release
+            // at revision 0 (`1.2.3` -> `1.2.4-rc.0`), so the default
+            // `--pre-release-id rc` still produces the `-rc.0` starting
+            // point either way.
+            match PrereleaseChannel::parse(&cli.pre_release_id) {
+                Some(channel) => { version_manager.bump_prerelease_channel(channel)?; }
+                None => { version_manager.bump_prerelease(&cli.pre_release_id); }
+            };
+        }
+        BumpType::Finalize => { version_manager.finalize_prerelease(); }
+        BumpType::Auto => {
+            let suggested = git_manager.suggest_bump_level()?;
+            println!("Auto-detected bump level from Conventional Commits: {}", suggested.as_str());
+            match suggested {
+                SuggestedBump::Major => { version_manager.bump_major(); }
+                SuggestedBump::Minor => { version_manager.bump_minor(); }
+                SuggestedBump::Patch => { version_manager.bump_patch(); }
+            };
+        }
     };
 
-    let version_string = new_version.to_string();
+    if let Some(metadata) = &cli.build_metadata {
+        version_manager.set_build_metadata(metadata)?;
+    }
+
+    let version_string = version_manager.get_current_version().to_string();
     println!("Updating version: {} -> {}", current_version, version_string);
-    
+
     // Generate and preview changelog
-    let changes = changelog_manager.generate_changes(Some(&current_version))?;
-    println!("\nChangelog preview:\n{}", changes);
-    
+    let changes = changelog_manager.generate_changes(Some(&current_version), cli.since_tag)?;
+    println!("\nChangelog preview:\n{}", changes.render());
+
+    if changes.has_breaking_changes() && matches!(cli.bump_type, BumpType::Patch | BumpType::Minor) {
+        println!("\nWarning: breaking changes detected since the last release, but a {} bump was selected. Consider running with `major` instead.", bump_type_name(cli.bump_type));
+    }
+
+    println!("\nFiles that will change:");
+    for project_file in &version_manager.project_files {
+        let old_content = fs::read_to_string(&project_file.path).unwrap_or_default();
+        let new_content = project_file.render_version(&version_string)?;
+        print_file_diff(&project_file.path, &old_content, &new_content);
+    }
+    let old_changelog = fs::read_to_string(&config.changelog_path).unwrap_or_default();
+    let changelog_existed = Path::new(&config.changelog_path).exists();
+    let new_changelog = changelog_manager.render_changelog(&version_string, &changes)?;
+    print_file_diff(&config.changelog_path, &old_changelog, &new_changelog);
+
+    if cli.dry_run {
+        println!("\nDry run: no files were written, and no commit or tag was created.");
+        return Ok(());
+    }
+
     println!("\nPress Enter to continue or Ctrl+C to cancel...");
     let mut input = String::new();
     std::io::stdin().read_line(&mut input)?;
 
+    version_manager.snapshot()?;
     version_manager.update_all_versions(&version_string)?;
     changelog_manager.update_changelog(&version_string, &changes)?;
-    
-    // Git operations
-    git_manager.commit_version_bump(&version_string)?;
-    git_manager.create_tag(&version_string)?;
-    
-    println!("Successfully bumped version to {}", version_string);
-    println!("Created git commit and tag v{}", version_string);
-    println!("Updated CHANGELOG.md");
-
-    if cli.push || cli.release {
-        git_manager.push_changes(&version_string)?;
-        println!("Pushed changes and tag to remote");
-    }
 
-    if cli.release {
-        git_manager.create_github_release(&version_string, &changes)?;
-        println!("Created GitHub release");
+    // Git operations. If any step from here on fails, roll back the tag,
+    // the release commit, and the file contents so the repo is left exactly
+    // as it started and the release is safe to retry.
+    let mut commit_created = false;
+    let mut tag_created = false;
+
+    let release_result: Result<()> = (|| {
+        git_manager.commit_version_bump(&version_string)?;
+        commit_created = true;
+        git_manager.create_tag(&version_string)?;
+        tag_created = true;
+
+        println!("Successfully bumped version to {}", version_string);
+        println!("Created git commit and tag v{}", version_string);
+        println!("Updated CHANGELOG.md");
+
+        if cli.push || cli.release {
+            git_manager.push_changes(&version_string)?;
+            println!("Pushed changes and tag to remote");
+        }
+
+        if cli.release {
+            git_manager.create_github_release(&version_string, &changes.render())?;
+            println!("Created GitHub release");
+        }
+
+        Ok(())
+    })();
+
+    if let Err(err) = release_result {
+        eprintln!("Release failed: {err:#}");
+        eprintln!("Rolling back...");
+        rollback_release(&git_manager, &version_manager, &version_string, commit_created, tag_created);
+        let changelog_restore = if changelog_existed {
+            fs::write(&config.changelog_path, &old_changelog)
+        } else {
+            fs::remove_file(&config.changelog_path)
+        };
+        if let Err(e) = changelog_restore {
+            eprintln!("Warning: failed to restore {}: {e}", config.changelog_path);
+        }
+        return Err(err);
     }
-    
+
     Ok(())
 }
+
+/// Undo a partially-completed release: delete the local tag and reset the
+/// release commit if they were created, then restore every project file to
+/// its pre-bump contents.
+fn rollback_release(
+    git_manager: &GitManager,
+    version_manager: &VersionManager,
+    version: &str,
+    commit_created: bool,
+    tag_created: bool,
+) {
+    if tag_created {
+        if let Err(e) = git_manager.delete_tag(version) {
+            eprintln!("Warning: failed to delete local tag: {e}");
+        }
+    }
+    if commit_created {
+        if let Err(e) = git_manager.reset_release_commit() {
+            eprintln!("Warning: failed to reset release commit: {e}");
+        }
+    }
+    if let Err(e) = version_manager.revert() {
+        eprintln!("Warning: failed to restore project file contents: {e}");
+    }
+}
+
+fn bump_type_name(bump_type: BumpType) -> &'static str {
+    match bump_type {
+        BumpType::Major => "major",
+        BumpType::Minor => "minor",
+        BumpType::Patch => "patch",
+        BumpType::PreRelease => "pre-release",
+        BumpType::Finalize => "finalize",
+        BumpType::Auto => "auto",
+    }
+}
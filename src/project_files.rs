@@ -10,153 +10,341 @@ pub enum ProjectType {
     Cpp,
     Meson,
     PlatformIO,
+    Node,
+    Maven,
+    Gradle,
+    /// Go modules store their version in a git tag, not in `go.mod`; writing
+    /// this type is a no-op.
+    Go,
+    /// A user-declared file kept in sync via a `.bump.toml` replacement rule.
+    /// The regex must contain one capture group wrapping the version token.
+    Custom(String),
+}
+
+impl ProjectType {
+    /// The `.bump.toml` key naming this type, used to look up a
+    /// per-project-type `version_format` override for auto-detected files.
+    pub fn config_key(&self) -> &str {
+        match self {
+            ProjectType::Rust => "rust",
+            ProjectType::Python => "python",
+            ProjectType::Cpp => "cpp",
+            ProjectType::Meson => "meson",
+            ProjectType::PlatformIO => "platformio",
+            ProjectType::Node => "node",
+            ProjectType::Maven => "maven",
+            ProjectType::Gradle => "gradle",
+            ProjectType::Go => "go",
+            ProjectType::Custom(_) => "custom",
+        }
+    }
 }
 
 pub struct ProjectFile {
     pub path: String,
     pub project_type: ProjectType,
+    /// Template controlling how the version is written back to this file,
+    /// e.g. `v${raw}` for tools that expect a `v`-prefixed tag-style string.
+    /// `${raw}` is the full semver (including pre-release/build); `${major}`,
+    /// `${minor}`, and `${patch}` are the numeric core. `None` writes the bare
+    /// semver unchanged, which is the right default for manifest files.
+    pub version_format: Option<String>,
 }
 
 impl ProjectFile {
+    pub fn new(path: String, project_type: ProjectType) -> Self {
+        ProjectFile {
+            path,
+            project_type,
+            version_format: None,
+        }
+    }
+
+    pub fn with_version_format(mut self, version_format: Option<String>) -> Self {
+        self.version_format = version_format;
+        self
+    }
+
     pub fn update_version(&self, new_version: &str) -> Result<()> {
-        match self.project_type {
-            ProjectType::Rust => self.update_cargo_toml(new_version),
-            ProjectType::Python => self.update_pyproject_toml(new_version),
-            ProjectType::Cpp => self.update_cmake_lists(new_version),
-            ProjectType::Meson => self.update_meson_build(new_version),
-            ProjectType::PlatformIO => self.update_platformio_project(new_version),
+        let content = self.render_version(new_version)?;
+        fs::write(&self.path, content)
+            .with_context(|| format!("Failed to write {}", self.path))
+    }
+
+    /// Compute the file's content with `new_version` applied, without
+    /// writing it. Used for both `update_version` and `--dry-run` previews.
+    pub fn render_version(&self, new_version: &str) -> Result<String> {
+        let formatted = self.format_version(new_version)?;
+        match &self.project_type {
+            ProjectType::Rust => self.render_cargo_toml(&formatted),
+            ProjectType::Python => self.render_pyproject_toml(&formatted),
+            // CMake's project(VERSION) only accepts a numeric major.minor.patch
+            // triple, so `version_format` doesn't apply here.
+            ProjectType::Cpp => self.render_cmake_lists(new_version),
+            ProjectType::Meson => self.render_meson_build(new_version, &formatted),
+            ProjectType::PlatformIO => self.render_platformio_project(&formatted),
+            ProjectType::Node => self.render_package_json(&formatted),
+            ProjectType::Maven => self.render_pom_xml(&formatted),
+            ProjectType::Gradle => self.render_gradle(&formatted),
+            ProjectType::Go => fs::read_to_string(&self.path)
+                .with_context(|| format!("Failed to read {}", self.path)),
+            ProjectType::Custom(pattern) => self.render_custom(&formatted, pattern),
         }
     }
 
-    fn update_cargo_toml(&self, new_version: &str) -> Result<()> {
+    /// Resolve this file's `version_format` template (default `${raw}`,
+    /// i.e. unprefixed) against the parsed `new_version`, substituting
+    /// `${raw}`, `${major}`, `${minor}`, and `${patch}`.
+    fn format_version(&self, new_version: &str) -> Result<String> {
+        let template = self.version_format.as_deref().unwrap_or("${raw}");
+        let parsed = semver::Version::parse(new_version)
+            .with_context(|| format!("Invalid version format: {}", new_version))?;
+
+        Ok(template
+            .replace("${raw}", new_version)
+            .replace("${major}", &parsed.major.to_string())
+            .replace("${minor}", &parsed.minor.to_string())
+            .replace("${patch}", &parsed.patch.to_string()))
+    }
+
+    /// Replace the captured version token in `self.path` using a
+    /// user-supplied regex, preserving everything else in the match
+    /// (surrounding text, quotes, etc).
+    fn render_custom(&self, new_version: &str, pattern: &str) -> Result<String> {
+        let content = fs::read_to_string(&self.path)
+            .with_context(|| format!("Failed to read {}", self.path))?;
+
+        let re = Regex::new(pattern)
+            .with_context(|| format!("Invalid custom replacement regex for {}", self.path))?;
+
+        if re.captures(&content).is_none() {
+            return Err(anyhow::anyhow!(
+                "Custom replacement regex matched nothing in {}",
+                self.path
+            ));
+        }
+
+        let mut result = String::with_capacity(content.len());
+        let mut last_end = 0;
+        for caps in re.captures_iter(&content) {
+            let group = caps.get(1).with_context(|| format!(
+                "Custom replacement regex for {} has no capture group to replace -- add one around the version token",
+                self.path
+            ))?;
+            result.push_str(&content[last_end..group.start()]);
+            result.push_str(new_version);
+            last_end = group.end();
+        }
+        result.push_str(&content[last_end..]);
+
+        Ok(result)
+    }
+
+    fn render_cargo_toml(&self, new_version: &str) -> Result<String> {
         let content = fs::read_to_string(&self.path)
             .context("Failed to read Cargo.toml")?;
         let mut doc = content.parse::<Document>()
             .context("Failed to parse Cargo.toml")?;
-        
-        doc["package"]["version"] = toml_edit::value(new_version);
-        
-        fs::write(&self.path, doc.to_string())
-            .context("Failed to write Cargo.toml")
+
+        // A member that inherits its version via `version.workspace = true`
+        // has no `package.version` string to overwrite; a workspace root can
+        // be a package, a virtual manifest, or both, so update whichever of
+        // the two tables is actually present.
+        if doc.get("package").and_then(|p| p.get("version")).and_then(|v| v.as_str()).is_some() {
+            doc["package"]["version"] = toml_edit::value(new_version);
+        }
+        if doc.get("workspace").and_then(|w| w.get("package")).is_some() {
+            doc["workspace"]["package"]["version"] = toml_edit::value(new_version);
+        }
+
+        Ok(doc.to_string())
     }
 
-    fn update_pyproject_toml(&self, new_version: &str) -> Result<()> {
+    fn render_pyproject_toml(&self, new_version: &str) -> Result<String> {
         let content = fs::read_to_string(&self.path)
             .context("Failed to read pyproject.toml")?;
         let mut doc = content.parse::<Document>()
             .context("Failed to parse pyproject.toml")?;
-        
+
         if let Some(tool) = doc.get_mut("tool") {
             if let Some(Item::Table(poetry)) = tool.get_mut("poetry") {
                 poetry["version"] = toml_edit::value(new_version);
             }
         }
-        
-        fs::write(&self.path, doc.to_string())
-            .context("Failed to write pyproject.toml")
+
+        Ok(doc.to_string())
     }
 
-    fn update_cmake_lists(&self, new_version: &str) -> Result<()> {
+    fn render_cmake_lists(&self, new_version: &str) -> Result<String> {
         let content = fs::read_to_string(&self.path)
             .context("Failed to read CMakeLists.txt")?;
-        
-        // Extract major, minor, and patch versions
-        let version_parts: Vec<&str> = new_version.split('.').collect();
-        if version_parts.len() < 3 {
-            return Err(anyhow::anyhow!("Invalid version format: {}", new_version));
-        }
-        
-        let major = version_parts[0];
-        let minor = version_parts[1];
-        let patch = version_parts[2];
-        
+
+        // CMake's project(VERSION) only accepts numeric major.minor.patch,
+        // so parse the full semver and use just the core triple.
+        let parsed = semver::Version::parse(new_version)
+            .with_context(|| format!("Invalid version format: {}", new_version))?;
+        if !parsed.pre.is_empty() || !parsed.build.is_empty() {
+            eprintln!(
+                "Warning: CMake's project(VERSION) only accepts numeric components; writing {}.{}.{} to {} and dropping the pre-release/build suffix",
+                parsed.major, parsed.minor, parsed.patch, self.path
+            );
+        }
+        let major = parsed.major.to_string();
+        let minor = parsed.minor.to_string();
+        let patch = parsed.patch.to_string();
+
         // Update version-related variables in CMakeLists.txt
-        let updated_content = update_cmake_version(&content, major, minor, patch)?;
-        
-        fs::write(&self.path, updated_content)
-            .context("Failed to write CMakeLists.txt")
+        update_cmake_version(&content, &major, &minor, &patch)
     }
 
-    fn update_meson_build(&self, new_version: &str) -> Result<()> {
+    fn render_meson_build(&self, new_version: &str, formatted: &str) -> Result<String> {
         let content = fs::read_to_string(&self.path)
             .context("Failed to read meson.build")?;
-        
-        // Extract major, minor, and patch versions
-        let version_parts: Vec<&str> = new_version.split('.').collect();
-        if version_parts.len() < 3 {
-            return Err(anyhow::anyhow!("Invalid version format: {}", new_version));
-        }
-        
-        let major = version_parts[0];
-        let minor = version_parts[1];
-        let patch = version_parts[2];
-        
+
+        // meson's `version :` field accepts the full semver string (and
+        // honors version_format); only the version_major/minor/patch
+        // variables need the raw numeric core.
+        let parsed = semver::Version::parse(new_version)
+            .with_context(|| format!("Invalid version format: {}", new_version))?;
+        let major = parsed.major.to_string();
+        let minor = parsed.minor.to_string();
+        let patch = parsed.patch.to_string();
+
         // Update version-related variables in meson.build
-        let updated_content = update_meson_version(&content, new_version, major, minor, patch)?;
-        
-        fs::write(&self.path, updated_content)
-            .context("Failed to write meson.build")
+        update_meson_version(&content, formatted, &major, &minor, &patch)
     }
 
-    fn update_platformio_project(&self, new_version: &str) -> Result<()> {
+    fn render_platformio_project(&self, new_version: &str) -> Result<String> {
         if self.path.ends_with("platformio.ini") {
-            self.update_platformio_ini(new_version)
+            self.render_platformio_ini(new_version)
         } else if self.path.ends_with("library.json") {
-            self.update_library_json(new_version)
+            self.render_library_json(new_version)
         } else if self.path.ends_with("library.properties") {
-            self.update_library_properties(new_version)
+            self.render_library_properties(new_version)
         } else {
             Err(anyhow::anyhow!("Unsupported PlatformIO file: {}", self.path))
         }
     }
 
-    fn update_platformio_ini(&self, new_version: &str) -> Result<()> {
+    fn render_platformio_ini(&self, new_version: &str) -> Result<String> {
         let content = fs::read_to_string(&self.path)
             .context("Failed to read platformio.ini")?;
-        
+
         // platformio.ini uses a simple INI format
         let re = Regex::new(r"(version\s*=\s*)(.+)").unwrap();
-        let updated_content = re.replace_all(&content, |caps: &regex::Captures| {
+        Ok(re.replace_all(&content, |caps: &regex::Captures| {
             format!("{}\"{}\"", &caps[1], new_version)
-        }).to_string();
-        
-        fs::write(&self.path, updated_content)
-            .context("Failed to write platformio.ini")
+        }).to_string())
     }
 
-    fn update_library_json(&self, new_version: &str) -> Result<()> {
+    fn render_library_json(&self, new_version: &str) -> Result<String> {
         let content = fs::read_to_string(&self.path)
             .context("Failed to read library.json")?;
-        
+
         // Parse the JSON file
         let mut json: serde_json::Value = serde_json::from_str(&content)
             .context("Failed to parse library.json")?;
-        
+
         // Update the version field
         if let Some(obj) = json.as_object_mut() {
             obj.insert("version".to_string(), serde_json::Value::String(new_version.to_string()));
         }
-        
+
         // Serialize back to JSON
-        let updated_content = serde_json::to_string_pretty(&json)
-            .context("Failed to serialize library.json")?;
-        
-        fs::write(&self.path, updated_content)
-            .context("Failed to write library.json")
+        serde_json::to_string_pretty(&json)
+            .context("Failed to serialize library.json")
     }
 
-    fn update_library_properties(&self, new_version: &str) -> Result<()> {
+    fn render_library_properties(&self, new_version: &str) -> Result<String> {
         let content = fs::read_to_string(&self.path)
             .context("Failed to read library.properties")?;
-        
+
         // library.properties uses a simple key=value format
         let re = Regex::new(r"(version\s*=\s*)(.+)").unwrap();
-        let updated_content = re.replace_all(&content, |caps: &regex::Captures| {
+        Ok(re.replace_all(&content, |caps: &regex::Captures| {
             format!("{}{}", &caps[1], new_version)
-        }).to_string();
-        
-        fs::write(&self.path, updated_content)
-            .context("Failed to write library.properties")
+        }).to_string())
+    }
+
+    fn render_package_json(&self, new_version: &str) -> Result<String> {
+        let content = fs::read_to_string(&self.path)
+            .context("Failed to read package.json")?;
+
+        let mut json: serde_json::Value = serde_json::from_str(&content)
+            .context("Failed to parse package.json")?;
+
+        if let Some(obj) = json.as_object_mut() {
+            obj.insert("version".to_string(), serde_json::Value::String(new_version.to_string()));
+        }
+
+        serde_json::to_string_pretty(&json)
+            .context("Failed to serialize package.json")
+    }
+
+    /// Replace the text of the first `<version>` that is a direct child of
+    /// `<project>`, re-emitting every other event unchanged so sibling
+    /// elements, comments, and formatting survive.
+    fn render_pom_xml(&self, new_version: &str) -> Result<String> {
+        use quick_xml::events::{BytesText, Event};
+        use quick_xml::reader::Reader;
+        use quick_xml::writer::Writer;
+        use std::io::Cursor;
+
+        let content = fs::read_to_string(&self.path)
+            .context("Failed to read pom.xml")?;
+
+        let mut reader = Reader::from_str(&content);
+        reader.config_mut().trim_text(false);
+        let mut writer = Writer::new(Cursor::new(Vec::new()));
+
+        let mut path: Vec<String> = Vec::new();
+        let mut buf = Vec::new();
+        let mut replaced = false;
+
+        loop {
+            match reader.read_event_into(&mut buf)? {
+                Event::Eof => break,
+                Event::Start(e) => {
+                    path.push(String::from_utf8_lossy(e.name().as_ref()).into_owned());
+                    let is_project_version = path == ["project", "version"];
+                    writer.write_event(Event::Start(e))?;
+
+                    if is_project_version && !replaced {
+                        // Consume and discard the original text, write the new version instead.
+                        if let Event::Text(_) = reader.read_event_into(&mut Vec::new())? {
+                            writer.write_event(Event::Text(BytesText::new(new_version)))?;
+                            replaced = true;
+                        }
+                    }
+                }
+                Event::End(e) => {
+                    path.pop();
+                    writer.write_event(Event::End(e))?;
+                }
+                event => writer.write_event(event)?,
+            }
+            buf.clear();
+        }
+
+        if !replaced {
+            return Err(anyhow::anyhow!("No <version> found under <project> in {}", self.path));
+        }
+
+        Ok(String::from_utf8(writer.into_inner().into_inner())?)
+    }
+
+    fn render_gradle(&self, new_version: &str) -> Result<String> {
+        let content = fs::read_to_string(&self.path)
+            .with_context(|| format!("Failed to read {}", self.path))?;
+
+        let re = Regex::new(r#"(version\s*=\s*["'])[^"']+(["'])"#).unwrap();
+        if re.captures(&content).is_none() {
+            return Err(anyhow::anyhow!("No version assignment found in {}", self.path));
+        }
+
+        Ok(re.replace_all(&content, |caps: &regex::Captures| {
+            format!("{}{}{}", &caps[1], new_version, &caps[2])
+        }).to_string())
     }
 }
 
@@ -165,7 +353,7 @@ fn update_cmake_version(content: &str, major: &str, minor: &str, patch: &str) ->
     let mut updated = String::new();
     let mut updated_project_version = false;
     let mut updated_version_vars = false;
-    
+
     for line in content.lines() {
         if !updated_project_version && line.trim().starts_with("project(") && line.contains("VERSION") {
             // Update project version - format: project(ProjectName VERSION X.Y.Z)
@@ -177,7 +365,7 @@ fn update_cmake_version(content: &str, major: &str, minor: &str, patch: &str) ->
             } else {
                 updated.push_str(line);
             }
-        } else if !updated_version_vars && 
+        } else if !updated_version_vars &&
                  (line.trim().starts_with("set(PROJECT_VERSION ") ||
                   line.trim().starts_with("set(CMAKE_PROJECT_VERSION ")) &&
                   !line.trim().contains("_MAJOR") &&
@@ -198,14 +386,14 @@ fn update_cmake_version(content: &str, major: &str, minor: &str, patch: &str) ->
         }
         updated.push('\n');
     }
-    
+
     Ok(updated)
 }
 
 // Helper function to update version in meson.build
 fn update_meson_version(content: &str, full_version: &str, major: &str, minor: &str, patch: &str) -> Result<String> {
     let mut updated = String::new();
-    
+
     // Handle line by line
     for line in content.lines() {
         // Match project version line
@@ -239,6 +427,6 @@ fn update_meson_version(content: &str, full_version: &str, major: &str, minor: &
         }
         updated.push('\n');
     }
-    
+
     Ok(updated)
-} 
\ No newline at end of file
+}
@@ -1,6 +1,6 @@
 use std::fs;
 use tempfile::tempdir;
-use crate::version::VersionManager;
+use crate::version::{PrereleaseChannel, VersionManager};
 use crate::project_files::{ProjectFile, ProjectType};
 
 // Rust project tests
@@ -39,16 +39,92 @@ edition = "2021"
 [dependencies]
 "#).unwrap();
 
-    let project_file = ProjectFile {
-        path: cargo_path.to_string_lossy().into_owned(),
-        project_type: ProjectType::Rust,
-    };
+    let project_file = ProjectFile::new(cargo_path.to_string_lossy().into_owned(), ProjectType::Rust);
     
     project_file.update_version("2.3.4").unwrap();
     let content = fs::read_to_string(&cargo_path).unwrap();
     assert!(content.contains("version = \"2.3.4\""));
 }
 
+#[test]
+fn test_detect_workspace_members_inherit_shared_version() {
+    let dir = tempdir().unwrap();
+    fs::write(dir.path().join("Cargo.toml"), r#"
+[workspace]
+members = ["crates/*"]
+
+[workspace.package]
+version = "1.2.3"
+"#).unwrap();
+
+    let member_dir = dir.path().join("crates/core");
+    fs::create_dir_all(&member_dir).unwrap();
+    fs::write(member_dir.join("Cargo.toml"), r#"
+[package]
+name = "core"
+version.workspace = true
+edition = "2021"
+"#).unwrap();
+
+    let mut version_manager = VersionManager::new();
+    version_manager.detect_version_files(dir.path()).unwrap();
+
+    assert_eq!(version_manager.get_current_version().to_string(), "1.2.3");
+    assert_eq!(version_manager.project_files.len(), 2);
+}
+
+#[test]
+fn test_update_all_versions_bumps_workspace_members_in_lockstep() {
+    let dir = tempdir().unwrap();
+    fs::write(dir.path().join("Cargo.toml"), r#"
+[workspace]
+members = ["crates/*"]
+
+[workspace.package]
+version = "1.2.3"
+"#).unwrap();
+
+    let member_dir = dir.path().join("crates/core");
+    fs::create_dir_all(&member_dir).unwrap();
+    let member_cargo_toml = member_dir.join("Cargo.toml");
+    fs::write(&member_cargo_toml, r#"
+[package]
+name = "core"
+version.workspace = true
+edition = "2021"
+"#).unwrap();
+
+    let mut version_manager = VersionManager::new();
+    version_manager.detect_version_files(dir.path()).unwrap();
+    version_manager.update_all_versions("2.0.0").unwrap();
+
+    let root_content = fs::read_to_string(dir.path().join("Cargo.toml")).unwrap();
+    assert!(root_content.contains("version = \"2.0.0\""));
+    let member_content = fs::read_to_string(&member_cargo_toml).unwrap();
+    assert!(member_content.contains("version.workspace = true"));
+}
+
+#[test]
+fn test_detect_reports_drift_against_stale_cargo_lock() {
+    let dir = tempdir().unwrap();
+    fs::write(dir.path().join("Cargo.toml"), r#"
+[package]
+name = "test_project"
+version = "1.2.3"
+edition = "2021"
+"#).unwrap();
+    fs::write(dir.path().join("Cargo.lock"), r#"
+[[package]]
+name = "test_project"
+version = "1.2.2"
+"#).unwrap();
+
+    let mut version_manager = VersionManager::new();
+    version_manager.detect_version_files(dir.path()).unwrap();
+
+    assert!(version_manager.check_consistency().is_err());
+}
+
 // Python project tests
 #[test]
 fn test_detect_python_version() {
@@ -89,10 +165,7 @@ version = "2.3.4"
 description = "Test project"
 "#).unwrap();
 
-    let project_file = ProjectFile {
-        path: pyproject_path.to_string_lossy().into_owned(),
-        project_type: ProjectType::Python,
-    };
+    let project_file = ProjectFile::new(pyproject_path.to_string_lossy().into_owned(), ProjectType::Python);
     
     project_file.update_version("3.4.5").unwrap();
     let content = fs::read_to_string(&pyproject_path).unwrap();
@@ -120,6 +193,21 @@ project(TestProject VERSION 3.4.5)
     assert_eq!(version.patch, 5);
 }
 
+#[test]
+fn test_detect_cmake_prerelease_version() {
+    let dir = tempdir().unwrap();
+    let cmake_path = dir.path().join("CMakeLists.txt");
+    fs::write(&cmake_path, r#"
+cmake_minimum_required(VERSION 3.10)
+project(TestProject VERSION 3.4.5-rc.0)
+"#).unwrap();
+
+    let mut version_manager = VersionManager::new();
+    version_manager.detect_version_files(dir.path()).unwrap();
+
+    assert_eq!(version_manager.get_current_version().to_string(), "3.4.5-rc.0");
+}
+
 #[test]
 fn test_detect_cmake_set_version() {
     let dir = tempdir().unwrap();
@@ -173,10 +261,7 @@ project(TestProject VERSION 3.4.5)
 # Other cmake config
 "#).unwrap();
 
-    let project_file = ProjectFile {
-        path: cmake_path.to_string_lossy().into_owned(),
-        project_type: ProjectType::Cpp,
-    };
+    let project_file = ProjectFile::new(cmake_path.to_string_lossy().into_owned(), ProjectType::Cpp);
     
     project_file.update_version("6.7.8").unwrap();
     let content = fs::read_to_string(&cmake_path).unwrap();
@@ -194,10 +279,7 @@ project(TestProject)
 set(PROJECT_VERSION 4.5.6)
 "#).unwrap();
 
-    let project_file = ProjectFile {
-        path: cmake_path.to_string_lossy().into_owned(),
-        project_type: ProjectType::Cpp,
-    };
+    let project_file = ProjectFile::new(cmake_path.to_string_lossy().into_owned(), ProjectType::Cpp);
     
     project_file.update_version("7.8.9").unwrap();
     let content = fs::read_to_string(&cmake_path).unwrap();
@@ -217,10 +299,7 @@ set(PROJECT_VERSION_MINOR 6)
 set(PROJECT_VERSION_PATCH 7)
 "#).unwrap();
 
-    let project_file = ProjectFile {
-        path: cmake_path.to_string_lossy().into_owned(),
-        project_type: ProjectType::Cpp,
-    };
+    let project_file = ProjectFile::new(cmake_path.to_string_lossy().into_owned(), ProjectType::Cpp);
     
     project_file.update_version("8.9.10").unwrap();
     let content = fs::read_to_string(&cmake_path).unwrap();
@@ -251,6 +330,22 @@ project('test_project', 'cpp',
     assert_eq!(version.patch, 7);
 }
 
+#[test]
+fn test_detect_meson_prerelease_version() {
+    let dir = tempdir().unwrap();
+    let meson_path = dir.path().join("meson.build");
+    fs::write(&meson_path, r#"
+project('test_project', 'cpp',
+  version : '5.6.7-beta.1',
+)
+"#).unwrap();
+
+    let mut version_manager = VersionManager::new();
+    version_manager.detect_version_files(dir.path()).unwrap();
+
+    assert_eq!(version_manager.get_current_version().to_string(), "5.6.7-beta.1");
+}
+
 #[test]
 fn test_detect_meson_version_variable() {
     let dir = tempdir().unwrap();
@@ -302,10 +397,7 @@ project('test_project', 'cpp',
 )
 "#).unwrap();
 
-    let project_file = ProjectFile {
-        path: meson_path.to_string_lossy().into_owned(),
-        project_type: ProjectType::Meson,
-    };
+    let project_file = ProjectFile::new(meson_path.to_string_lossy().into_owned(), ProjectType::Meson);
     
     project_file.update_version("8.9.10").unwrap();
     let content = fs::read_to_string(&meson_path).unwrap();
@@ -324,10 +416,7 @@ version_minor = '8'
 version_patch = '9'
 "#).unwrap();
 
-    let project_file = ProjectFile {
-        path: meson_path.to_string_lossy().into_owned(),
-        project_type: ProjectType::Meson,
-    };
+    let project_file = ProjectFile::new(meson_path.to_string_lossy().into_owned(), ProjectType::Meson);
     
     project_file.update_version("9.10.11").unwrap();
     let content = fs::read_to_string(&meson_path).unwrap();
@@ -403,6 +492,130 @@ edition = "2021"
     assert_eq!(new_version.patch, 4);
 }
 
+// `bump_prerelease` is the CLI's fallback for a `--pre-release-id` that
+// isn't a recognized channel (`alpha`/`beta`/`rc`) -- those route through
+// `bump_prerelease_channel` instead (see main.rs), so these tests use a
+// free-form label to exercise the path the CLI can actually reach.
+#[test]
+fn test_bump_prerelease_from_release() {
+    let mut version_manager = VersionManager::new();
+    let dir = tempdir().unwrap();
+    let cargo_path = dir.path().join("Cargo.toml");
+    fs::write(&cargo_path, r#"
+[package]
+name = "test_project"
+version = "1.2.3"
+edition = "2021"
+"#).unwrap();
+
+    version_manager.detect_version_files(dir.path()).unwrap();
+    version_manager.bump_prerelease("nightly");
+
+    let new_version = version_manager.get_current_version();
+    assert_eq!(new_version.to_string(), "1.2.4-nightly.0");
+}
+
+#[test]
+fn test_bump_prerelease_increments_numeric_identifier() {
+    let mut version_manager = VersionManager::new();
+    let dir = tempdir().unwrap();
+    let cargo_path = dir.path().join("Cargo.toml");
+    fs::write(&cargo_path, r#"
+[package]
+name = "test_project"
+version = "1.2.3-nightly.4"
+edition = "2021"
+"#).unwrap();
+
+    version_manager.detect_version_files(dir.path()).unwrap();
+    version_manager.bump_prerelease("nightly");
+
+    let new_version = version_manager.get_current_version();
+    assert_eq!(new_version.to_string(), "1.2.3-nightly.5");
+}
+
+#[test]
+fn test_set_and_clear_build_metadata() {
+    let mut version_manager = VersionManager::new();
+    let dir = tempdir().unwrap();
+    let cargo_path = dir.path().join("Cargo.toml");
+    fs::write(&cargo_path, r#"
+[package]
+name = "test_project"
+version = "1.2.3"
+edition = "2021"
+"#).unwrap();
+
+    version_manager.detect_version_files(dir.path()).unwrap();
+    version_manager.set_build_metadata("build.5").unwrap();
+    assert_eq!(version_manager.get_current_version().to_string(), "1.2.3+build.5");
+
+    version_manager.clear_build_metadata();
+    assert_eq!(version_manager.get_current_version().to_string(), "1.2.3");
+}
+
+#[test]
+fn test_update_cmake_version_drops_prerelease_suffix() {
+    let dir = tempdir().unwrap();
+    let cmake_path = dir.path().join("CMakeLists.txt");
+    fs::write(&cmake_path, r#"
+cmake_minimum_required(VERSION 3.10)
+project(TestProject VERSION 1.2.3)
+"#).unwrap();
+
+    let project_file = ProjectFile::new(cmake_path.to_string_lossy().into_owned(), ProjectType::Cpp);
+
+    project_file.update_version("1.2.4-rc.0").unwrap();
+    let content = fs::read_to_string(&cmake_path).unwrap();
+    assert!(content.contains("project(TestProject VERSION 1.2.4"));
+    assert!(!content.contains("rc"));
+}
+
+#[test]
+fn test_finalize_prerelease_strips_suffix() {
+    let mut version_manager = VersionManager::new();
+    let dir = tempdir().unwrap();
+    let cargo_path = dir.path().join("Cargo.toml");
+    fs::write(&cargo_path, r#"
+[package]
+name = "test_project"
+version = "1.2.3-rc.4"
+edition = "2021"
+"#).unwrap();
+
+    version_manager.detect_version_files(dir.path()).unwrap();
+    version_manager.finalize_prerelease();
+
+    let new_version = version_manager.get_current_version();
+    assert_eq!(new_version.to_string(), "1.2.3");
+}
+
+#[test]
+fn test_update_custom_project_file() {
+    let dir = tempdir().unwrap();
+    let header_path = dir.path().join("version.h");
+    fs::write(&header_path, r#"#define APP_VERSION "1.2.3"
+"#).unwrap();
+
+    let project_file = ProjectFile::new(header_path.to_string_lossy().into_owned(), ProjectType::Custom(r#"APP_VERSION "([\d\.]+)""#.to_string()));
+
+    project_file.update_version("2.3.4").unwrap();
+    let content = fs::read_to_string(&header_path).unwrap();
+    assert!(content.contains(r#"APP_VERSION "2.3.4""#));
+}
+
+#[test]
+fn test_custom_project_file_without_capture_group_errors() {
+    let dir = tempdir().unwrap();
+    let header_path = dir.path().join("version.h");
+    fs::write(&header_path, r#"#define APP_VERSION "1.2.3"
+"#).unwrap();
+
+    let project_file = ProjectFile::new(header_path.to_string_lossy().into_owned(), ProjectType::Custom(r#"APP_VERSION "[\d\.]+""#.to_string()));
+
+    assert!(project_file.update_version("2.3.4").is_err());
+}
+
 // Multiple project types test
 #[test]
 fn test_multiple_project_types() {
@@ -507,16 +720,29 @@ framework = arduino
 version = "1.2.3"
 "#).unwrap();
 
-    let project_file = ProjectFile {
-        path: platformio_ini_path.to_string_lossy().into_owned(),
-        project_type: ProjectType::PlatformIO,
-    };
+    let project_file = ProjectFile::new(platformio_ini_path.to_string_lossy().into_owned(), ProjectType::PlatformIO);
     
     project_file.update_version("2.3.4").unwrap();
     let content = fs::read_to_string(&platformio_ini_path).unwrap();
     assert!(content.contains("version = \"2.3.4\""));
 }
 
+#[test]
+fn test_detect_platformio_ini_prerelease_version() {
+    let dir = tempdir().unwrap();
+    let platformio_ini_path = dir.path().join("platformio.ini");
+    fs::write(&platformio_ini_path, r#"
+[env:uno]
+platform = atmelavr
+version = "1.2.3-rc.0"
+"#).unwrap();
+
+    let mut version_manager = VersionManager::new();
+    version_manager.detect_version_files(dir.path()).unwrap();
+
+    assert_eq!(version_manager.get_current_version().to_string(), "1.2.3-rc.0");
+}
+
 #[test]
 fn test_detect_library_json_version() {
     let dir = tempdir().unwrap();
@@ -563,10 +789,7 @@ fn test_update_library_json_version() {
 }
 "#).unwrap();
 
-    let project_file = ProjectFile {
-        path: library_json_path.to_string_lossy().into_owned(),
-        project_type: ProjectType::PlatformIO,
-    };
+    let project_file = ProjectFile::new(library_json_path.to_string_lossy().into_owned(), ProjectType::PlatformIO);
     
     project_file.update_version("3.4.5").unwrap();
     let content = fs::read_to_string(&library_json_path).unwrap();
@@ -598,6 +821,21 @@ architectures=*
     assert_eq!(version.patch, 5);
 }
 
+#[test]
+fn test_detect_library_properties_prerelease_version() {
+    let dir = tempdir().unwrap();
+    let properties_path = dir.path().join("library.properties");
+    fs::write(&properties_path, r#"
+name=TestLibrary
+version=3.4.5-alpha.2
+"#).unwrap();
+
+    let mut version_manager = VersionManager::new();
+    version_manager.detect_version_files(dir.path()).unwrap();
+
+    assert_eq!(version_manager.get_current_version().to_string(), "3.4.5-alpha.2");
+}
+
 #[test]
 fn test_update_library_properties_version() {
     let dir = tempdir().unwrap();
@@ -608,10 +846,7 @@ version=3.4.5
 author=Test Author <test@example.com>
 "#).unwrap();
 
-    let project_file = ProjectFile {
-        path: properties_path.to_string_lossy().into_owned(),
-        project_type: ProjectType::PlatformIO,
-    };
+    let project_file = ProjectFile::new(properties_path.to_string_lossy().into_owned(), ProjectType::PlatformIO);
     
     project_file.update_version("4.5.6").unwrap();
     let content = fs::read_to_string(&properties_path).unwrap();
@@ -672,4 +907,494 @@ author=Test Author <test@example.com>
     
     let properties_content = fs::read_to_string(&properties_path).unwrap();
     assert!(properties_content.contains("version=1.3.0"));
+}
+
+// Node project tests
+#[test]
+fn test_detect_package_json_version() {
+    let dir = tempdir().unwrap();
+    let package_json_path = dir.path().join("package.json");
+    fs::write(&package_json_path, r#"
+{
+  "name": "test-project",
+  "version": "1.2.3",
+  "description": "Test node project"
+}
+"#).unwrap();
+
+    let mut version_manager = VersionManager::new();
+    version_manager.detect_version_files(dir.path()).unwrap();
+
+    let version = version_manager.get_current_version();
+    assert_eq!(version.major, 1);
+    assert_eq!(version.minor, 2);
+    assert_eq!(version.patch, 3);
+}
+
+#[test]
+fn test_update_package_json_version() {
+    let dir = tempdir().unwrap();
+    let package_json_path = dir.path().join("package.json");
+    fs::write(&package_json_path, r#"
+{
+  "name": "test-project",
+  "version": "1.2.3",
+  "description": "Test node project"
+}
+"#).unwrap();
+
+    let project_file = ProjectFile::new(package_json_path.to_string_lossy().into_owned(), ProjectType::Node);
+
+    project_file.update_version("2.3.4").unwrap();
+    let content = fs::read_to_string(&package_json_path).unwrap();
+    assert!(content.contains("\"version\": \"2.3.4\""));
+}
+
+#[test]
+fn test_private_package_json_is_skipped() {
+    let dir = tempdir().unwrap();
+    fs::write(dir.path().join("package.json"), r#"
+{
+  "name": "internal-workspace-root",
+  "version": "1.2.3",
+  "private": true
+}
+"#).unwrap();
+
+    let mut version_manager = VersionManager::new();
+    version_manager.detect_version_files(dir.path()).unwrap();
+
+    assert_eq!(version_manager.project_files.len(), 0);
+    assert_eq!(version_manager.get_current_version().to_string(), "0.1.0");
+}
+
+// version_format tests
+#[test]
+fn test_version_format_prefixes_written_version() {
+    let dir = tempdir().unwrap();
+    let platformio_ini_path = dir.path().join("platformio.ini");
+    fs::write(&platformio_ini_path, "[platformio]\nversion = \"1.2.3\"\n").unwrap();
+
+    let project_file = ProjectFile::new(platformio_ini_path.to_string_lossy().into_owned(), ProjectType::PlatformIO)
+        .with_version_format(Some("v${raw}".to_string()));
+
+    project_file.update_version("2.3.4").unwrap();
+    let content = fs::read_to_string(&platformio_ini_path).unwrap();
+    assert!(content.contains("version = \"v2.3.4\""));
+}
+
+#[test]
+fn test_version_format_major_minor_patch_placeholders() {
+    let dir = tempdir().unwrap();
+    let gradle_path = dir.path().join("build.gradle");
+    fs::write(&gradle_path, "version = \"1.2.3\"\n").unwrap();
+
+    let project_file = ProjectFile::new(gradle_path.to_string_lossy().into_owned(), ProjectType::Gradle)
+        .with_version_format(Some("${major}.${minor}.${patch}".to_string()));
+
+    project_file.update_version("2.3.4-rc.1").unwrap();
+    let content = fs::read_to_string(&gradle_path).unwrap();
+    assert!(content.contains("version = \"2.3.4\""));
+}
+
+#[test]
+fn test_detect_tolerates_leading_v_in_package_json() {
+    let dir = tempdir().unwrap();
+    fs::write(dir.path().join("package.json"), r#"
+{
+  "name": "test-project",
+  "version": "v1.2.3"
+}
+"#).unwrap();
+
+    let mut version_manager = VersionManager::new();
+    version_manager.detect_version_files(dir.path()).unwrap();
+
+    assert_eq!(version_manager.get_current_version().to_string(), "1.2.3");
+}
+
+#[test]
+fn test_apply_config_sets_version_format_for_auto_detected_file() {
+    let dir = tempdir().unwrap();
+    let platformio_ini_path = dir.path().join("platformio.ini");
+    fs::write(&platformio_ini_path, "[platformio]\nversion = \"1.2.3\"\n").unwrap();
+
+    let mut config = crate::config::Config::default();
+    config.version_formats.insert("platformio".to_string(), "v${raw}".to_string());
+
+    let mut version_manager = VersionManager::new();
+    version_manager.detect_version_files(dir.path()).unwrap();
+    version_manager.apply_config(&config);
+
+    version_manager.update_all_versions("2.3.4").unwrap();
+    let content = fs::read_to_string(&platformio_ini_path).unwrap();
+    assert!(content.contains("version = \"v2.3.4\""));
+}
+
+// Maven project tests
+#[test]
+fn test_detect_pom_xml_version() {
+    let dir = tempdir().unwrap();
+    let pom_path = dir.path().join("pom.xml");
+    fs::write(&pom_path, r#"<project>
+  <modelVersion>4.0.0</modelVersion>
+  <groupId>com.example</groupId>
+  <artifactId>test-project</artifactId>
+  <version>1.2.3</version>
+  <dependencies>
+    <dependency>
+      <groupId>com.other</groupId>
+      <artifactId>dep</artifactId>
+      <version>9.9.9</version>
+    </dependency>
+  </dependencies>
+</project>
+"#).unwrap();
+
+    let mut version_manager = VersionManager::new();
+    version_manager.detect_version_files(dir.path()).unwrap();
+
+    let version = version_manager.get_current_version();
+    assert_eq!(version.major, 1);
+    assert_eq!(version.minor, 2);
+    assert_eq!(version.patch, 3);
+}
+
+#[test]
+fn test_update_pom_xml_version_ignores_dependency_versions() {
+    let dir = tempdir().unwrap();
+    let pom_path = dir.path().join("pom.xml");
+    fs::write(&pom_path, r#"<project>
+  <modelVersion>4.0.0</modelVersion>
+  <groupId>com.example</groupId>
+  <artifactId>test-project</artifactId>
+  <version>1.2.3</version>
+  <dependencies>
+    <dependency>
+      <groupId>com.other</groupId>
+      <artifactId>dep</artifactId>
+      <version>9.9.9</version>
+    </dependency>
+  </dependencies>
+</project>
+"#).unwrap();
+
+    let project_file = ProjectFile::new(pom_path.to_string_lossy().into_owned(), ProjectType::Maven);
+
+    project_file.update_version("2.3.4").unwrap();
+    let content = fs::read_to_string(&pom_path).unwrap();
+    assert!(content.contains("<version>2.3.4</version>"));
+    assert!(content.contains("<version>9.9.9</version>"));
+}
+
+// Gradle project tests
+#[test]
+fn test_detect_gradle_version() {
+    let dir = tempdir().unwrap();
+    let gradle_path = dir.path().join("build.gradle");
+    fs::write(&gradle_path, r#"
+plugins {
+    id 'java'
+}
+
+version = '1.2.3'
+"#).unwrap();
+
+    let mut version_manager = VersionManager::new();
+    version_manager.detect_version_files(dir.path()).unwrap();
+
+    let version = version_manager.get_current_version();
+    assert_eq!(version.major, 1);
+    assert_eq!(version.minor, 2);
+    assert_eq!(version.patch, 3);
+}
+
+#[test]
+fn test_update_gradle_version() {
+    let dir = tempdir().unwrap();
+    let gradle_path = dir.path().join("build.gradle");
+    fs::write(&gradle_path, r#"
+plugins {
+    id 'java'
+}
+
+version = '1.2.3'
+"#).unwrap();
+
+    let project_file = ProjectFile::new(gradle_path.to_string_lossy().into_owned(), ProjectType::Gradle);
+
+    project_file.update_version("2.3.4").unwrap();
+    let content = fs::read_to_string(&gradle_path).unwrap();
+    assert!(content.contains("version = '2.3.4'"));
+}
+
+// Go module tests
+#[test]
+fn test_detect_go_mod_registers_file_without_changing_version() {
+    let dir = tempdir().unwrap();
+    let go_mod_path = dir.path().join("go.mod");
+    fs::write(&go_mod_path, "module example.com/test\n\ngo 1.21\n").unwrap();
+
+    let mut version_manager = VersionManager::new();
+    version_manager.detect_version_files(dir.path()).unwrap();
+
+    assert_eq!(version_manager.project_files.len(), 1);
+    let version = version_manager.get_current_version();
+    assert_eq!(version.to_string(), "0.1.0");
+}
+
+#[test]
+fn test_update_go_mod_is_noop() {
+    let dir = tempdir().unwrap();
+    let go_mod_path = dir.path().join("go.mod");
+    let original = "module example.com/test\n\ngo 1.21\n";
+    fs::write(&go_mod_path, original).unwrap();
+
+    let project_file = ProjectFile::new(go_mod_path.to_string_lossy().into_owned(), ProjectType::Go);
+
+    project_file.update_version("2.3.4").unwrap();
+    let content = fs::read_to_string(&go_mod_path).unwrap();
+    assert_eq!(content, original);
+}
+
+// Snapshot/revert tests
+#[test]
+fn test_revert_restores_original_file_contents() {
+    let dir = tempdir().unwrap();
+    let cargo_path = dir.path().join("Cargo.toml");
+    fs::write(&cargo_path, r#"
+[package]
+name = "test_project"
+version = "1.2.3"
+edition = "2021"
+"#).unwrap();
+
+    let mut version_manager = VersionManager::new();
+    version_manager.detect_version_files(dir.path()).unwrap();
+    version_manager.snapshot().unwrap();
+
+    version_manager.bump_major();
+    let new_version = version_manager.get_current_version().to_string();
+    version_manager.update_all_versions(&new_version).unwrap();
+
+    let bumped_content = fs::read_to_string(&cargo_path).unwrap();
+    assert!(bumped_content.contains("version = \"2.0.0\""));
+
+    version_manager.revert().unwrap();
+    let restored_content = fs::read_to_string(&cargo_path).unwrap();
+    assert!(restored_content.contains("version = \"1.2.3\""));
+}
+
+#[test]
+fn test_revert_without_snapshot_is_noop() {
+    let dir = tempdir().unwrap();
+    let cargo_path = dir.path().join("Cargo.toml");
+    fs::write(&cargo_path, r#"
+[package]
+name = "test_project"
+version = "1.2.3"
+edition = "2021"
+"#).unwrap();
+
+    let mut version_manager = VersionManager::new();
+    version_manager.detect_version_files(dir.path()).unwrap();
+    version_manager.update_all_versions("2.0.0").unwrap();
+
+    version_manager.revert().unwrap();
+    let content = fs::read_to_string(&cargo_path).unwrap();
+    assert!(content.contains("version = \"2.0.0\""));
+}
+
+// Pre-release channel tests
+#[test]
+fn test_bump_prerelease_channel_from_final() {
+    let mut version_manager = VersionManager::new();
+    let dir = tempdir().unwrap();
+    let cargo_path = dir.path().join("Cargo.toml");
+    fs::write(&cargo_path, r#"
+[package]
+name = "test_project"
+version = "1.2.3"
+edition = "2021"
+"#).unwrap();
+
+    version_manager.detect_version_files(dir.path()).unwrap();
+    version_manager.bump_prerelease_channel(PrereleaseChannel::Alpha).unwrap();
+
+    assert_eq!(version_manager.get_current_version().to_string(), "1.2.4-alpha.0");
+}
+
+#[test]
+fn test_bump_prerelease_channel_same_channel_increments_revision() {
+    let mut version_manager = VersionManager::new();
+    let dir = tempdir().unwrap();
+    let cargo_path = dir.path().join("Cargo.toml");
+    fs::write(&cargo_path, r#"
+[package]
+name = "test_project"
+version = "1.2.3-beta.1"
+edition = "2021"
+"#).unwrap();
+
+    version_manager.detect_version_files(dir.path()).unwrap();
+    version_manager.bump_prerelease_channel(PrereleaseChannel::Beta).unwrap();
+
+    assert_eq!(version_manager.get_current_version().to_string(), "1.2.3-beta.2");
+}
+
+#[test]
+fn test_bump_prerelease_channel_to_higher_channel_resets_revision() {
+    let mut version_manager = VersionManager::new();
+    let dir = tempdir().unwrap();
+    let cargo_path = dir.path().join("Cargo.toml");
+    fs::write(&cargo_path, r#"
+[package]
+name = "test_project"
+version = "1.2.3-beta.3"
+edition = "2021"
+"#).unwrap();
+
+    version_manager.detect_version_files(dir.path()).unwrap();
+    version_manager.bump_prerelease_channel(PrereleaseChannel::Rc).unwrap();
+
+    assert_eq!(version_manager.get_current_version().to_string(), "1.2.3-rc.1");
+}
+
+#[test]
+fn test_bump_prerelease_channel_rejects_moving_backwards() {
+    let mut version_manager = VersionManager::new();
+    let dir = tempdir().unwrap();
+    let cargo_path = dir.path().join("Cargo.toml");
+    fs::write(&cargo_path, r#"
+[package]
+name = "test_project"
+version = "1.2.3-rc.1"
+edition = "2021"
+"#).unwrap();
+
+    version_manager.detect_version_files(dir.path()).unwrap();
+    let result = version_manager.bump_prerelease_channel(PrereleaseChannel::Beta);
+
+    assert!(result.is_err());
+    assert_eq!(version_manager.get_current_version().to_string(), "1.2.3-rc.1");
+}
+
+#[test]
+fn test_promote_to_final_strips_channel_suffix() {
+    let mut version_manager = VersionManager::new();
+    let dir = tempdir().unwrap();
+    let cargo_path = dir.path().join("Cargo.toml");
+    fs::write(&cargo_path, r#"
+[package]
+name = "test_project"
+version = "1.2.3-rc.2"
+edition = "2021"
+"#).unwrap();
+
+    version_manager.detect_version_files(dir.path()).unwrap();
+    version_manager.promote_to_final();
+
+    assert_eq!(version_manager.get_current_version().to_string(), "1.2.3");
+}
+
+#[test]
+fn test_detect_version_files_picks_higher_version_on_disagreement() {
+    let dir = tempdir().unwrap();
+    fs::write(dir.path().join("Cargo.toml"), r#"
+[package]
+name = "test_project"
+version = "1.2.3"
+edition = "2021"
+"#).unwrap();
+    fs::write(dir.path().join("package.json"), r#"
+{
+  "name": "test-project",
+  "version": "1.3.0"
+}
+"#).unwrap();
+
+    let mut version_manager = VersionManager::new();
+    version_manager.detect_version_files(dir.path()).unwrap();
+
+    assert_eq!(version_manager.get_current_version().to_string(), "1.3.0");
+}
+
+// Version consistency / drift tests
+#[test]
+fn test_check_consistency_passes_when_files_agree() {
+    let dir = tempdir().unwrap();
+    fs::write(dir.path().join("Cargo.toml"), r#"
+[package]
+name = "test_project"
+version = "1.2.3"
+edition = "2021"
+"#).unwrap();
+    fs::write(dir.path().join("package.json"), r#"
+{
+  "name": "test-project",
+  "version": "1.2.3"
+}
+"#).unwrap();
+
+    let mut version_manager = VersionManager::new();
+    version_manager.detect_version_files(dir.path()).unwrap();
+
+    assert!(version_manager.check_consistency().is_ok());
+}
+
+#[test]
+fn test_check_consistency_reports_drift() {
+    let dir = tempdir().unwrap();
+    fs::write(dir.path().join("Cargo.toml"), r#"
+[package]
+name = "test_project"
+version = "1.2.0"
+edition = "2021"
+"#).unwrap();
+    fs::write(dir.path().join("package.json"), r#"
+{
+  "name": "test-project",
+  "version": "1.1.9"
+}
+"#).unwrap();
+
+    let mut version_manager = VersionManager::new();
+    version_manager.detect_version_files(dir.path()).unwrap();
+
+    let drift = version_manager.check_consistency().unwrap_err();
+    assert_eq!(drift.versions.len(), 2);
+}
+
+#[test]
+fn test_reconcile_to_highest_version_resyncs_all_files() {
+    let dir = tempdir().unwrap();
+    let cargo_path = dir.path().join("Cargo.toml");
+    let package_json_path = dir.path().join("package.json");
+    fs::write(&cargo_path, r#"
+[package]
+name = "test_project"
+version = "1.2.0"
+edition = "2021"
+"#).unwrap();
+    fs::write(&package_json_path, r#"
+{
+  "name": "test-project",
+  "version": "1.3.0"
+}
+"#).unwrap();
+
+    let mut version_manager = VersionManager::new();
+    version_manager.detect_version_files(dir.path()).unwrap();
+    assert!(version_manager.check_consistency().is_err());
+
+    version_manager.reconcile_to_highest_version();
+    let reconciled = version_manager.get_current_version().to_string();
+    assert_eq!(reconciled, "1.3.0");
+
+    version_manager.update_all_versions(&reconciled).unwrap();
+
+    let cargo_content = fs::read_to_string(&cargo_path).unwrap();
+    assert!(cargo_content.contains("version = \"1.3.0\""));
+    let package_json_content = fs::read_to_string(&package_json_path).unwrap();
+    assert!(package_json_content.contains("\"version\": \"1.3.0\""));
 } 
\ No newline at end of file
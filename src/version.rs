@@ -1,15 +1,70 @@
-use semver::Version;
-use std::path::Path;
-use anyhow::Result;
+use semver::{BuildMetadata, Prerelease, Version};
+use std::path::{Path, PathBuf};
+use anyhow::{Context, Result};
 use crate::project_files::{ProjectFile, ProjectType};
 use std::fs;
 use toml_edit::{Document, Item};
 use regex::Regex;
+use serde::Deserialize;
 use serde_json;
 
+/// A pre-release channel, ordered `Alpha < Beta < Rc`, modeled on the
+/// release-type ordering in uvm_core. `Final` isn't a channel in this enum;
+/// it's represented by an empty `Version::pre` and reached via
+/// `promote_to_final`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum PrereleaseChannel {
+    Alpha,
+    Beta,
+    Rc,
+}
+
+impl PrereleaseChannel {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PrereleaseChannel::Alpha => "alpha",
+            PrereleaseChannel::Beta => "beta",
+            PrereleaseChannel::Rc => "rc",
+        }
+    }
+
+    pub fn parse(label: &str) -> Option<Self> {
+        match label {
+            "alpha" => Some(PrereleaseChannel::Alpha),
+            "beta" => Some(PrereleaseChannel::Beta),
+            "rc" => Some(PrereleaseChannel::Rc),
+            _ => None,
+        }
+    }
+}
+
+/// Reported by `check_consistency` when detected project files disagree on
+/// the current version, e.g. a stale `Cargo.toml` next to an already-bumped
+/// `library.json`.
+#[derive(Debug)]
+pub struct VersionDrift {
+    pub versions: Vec<(String, Version)>,
+}
+
+impl std::fmt::Display for VersionDrift {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Project files disagree on the current version:")?;
+        for (path, version) in &self.versions {
+            writeln!(f, "  {} -> {}", path, version)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for VersionDrift {}
+
 pub struct VersionManager {
     current_version: Version,
     pub project_files: Vec<ProjectFile>,
+    snapshot: Option<Vec<(String, String)>>,
+    // The version parsed out of each detected file during
+    // `detect_version_files`, used by `check_consistency` to find drift.
+    detected_versions: Vec<(String, Version)>,
 }
 
 impl VersionManager {
@@ -18,7 +73,59 @@ impl VersionManager {
         VersionManager {
             current_version: Version::new(0, 1, 0),
             project_files: Vec::new(),
+            snapshot: None,
+            detected_versions: Vec::new(),
+        }
+    }
+
+    /// Report every detected file whose version disagrees with the others,
+    /// as a structured `VersionDrift` error listing each path and version.
+    pub fn check_consistency(&self) -> std::result::Result<(), VersionDrift> {
+        let Some((_, first_version)) = self.detected_versions.first() else {
+            return Ok(());
+        };
+
+        if self.detected_versions.iter().any(|(_, v)| v != first_version) {
+            return Err(VersionDrift {
+                versions: self.detected_versions.clone(),
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Reconcile drifted project files by adopting the highest detected
+    /// version (per the channel-aware ordering `adopt_version` uses) as
+    /// `current_version`, so the next `update_all_versions` call resyncs
+    /// every file onto it in one pass.
+    pub fn reconcile_to_highest_version(&mut self) {
+        if let Some((_, highest)) = self.detected_versions.iter().max_by(|a, b| a.1.cmp(&b.1)) {
+            self.current_version = highest.clone();
+        }
+    }
+
+    /// Record the on-disk contents of every tracked project file, so a
+    /// failed release can be undone byte-for-byte with `revert()`.
+    pub fn snapshot(&mut self) -> Result<()> {
+        let mut files = Vec::with_capacity(self.project_files.len());
+        for project_file in &self.project_files {
+            let content = fs::read_to_string(&project_file.path)
+                .with_context(|| format!("Failed to read {}", project_file.path))?;
+            files.push((project_file.path.clone(), content));
         }
+        self.snapshot = Some(files);
+        Ok(())
+    }
+
+    /// Restore every file captured by `snapshot()` to its original contents.
+    pub fn revert(&self) -> Result<()> {
+        if let Some(files) = &self.snapshot {
+            for (path, content) in files {
+                fs::write(path, content)
+                    .with_context(|| format!("Failed to restore {}", path))?;
+            }
+        }
+        Ok(())
     }
 
     pub fn bump_major(&mut self) -> &Version {
@@ -39,6 +146,98 @@ impl VersionManager {
         &self.current_version
     }
 
+    /// Bump to the next pre-release identifier.
+    ///
+    /// If the current version is already a pre-release, the trailing numeric
+    /// identifier is incremented (`1.2.3-nightly.4` -> `1.2.3-nightly.5`). If
+    /// the current version is a normal release, patch is bumped first and a
+    /// starting pre-release label is attached (`1.2.3` -> `1.2.4-nightly.0`).
+    ///
+    /// This is the fallback for a `pre_release_id` that isn't a recognized
+    /// channel (`alpha`/`beta`/`rc`); the CLI routes those through
+    /// `bump_prerelease_channel` instead, which takes precedence (see
+    /// main.rs). Since `rc` is the CLI's default `--pre-release-id`, a plain
+    /// `bump` call never reaches this function in practice.
+    pub fn bump_prerelease(&mut self, pre_release_id: &str) -> &Version {
+        if self.current_version.pre.is_empty() {
+            self.current_version.patch += 1;
+            self.current_version.pre = Prerelease::new(&format!("{}.0", pre_release_id))
+                .expect("pre-release id produces a valid semver identifier");
+        } else {
+            let incremented = increment_prerelease_identifier(self.current_version.pre.as_str());
+            self.current_version.pre = Prerelease::new(&incremented)
+                .expect("incremented pre-release identifier is valid semver");
+        }
+        &self.current_version
+    }
+
+    /// Strip the pre-release suffix, producing the final release version.
+    pub fn finalize_prerelease(&mut self) -> &Version {
+        self.current_version.pre = Prerelease::EMPTY;
+        &self.current_version
+    }
+
+    /// Bump to the next revision of a named pre-release channel
+    /// (`Alpha`/`Beta`/`Rc`), appending a numeric `.N` revision to the semver
+    /// `pre` field (`1.4.0-beta.2`).
+    ///
+    /// Bumping within the same channel increments the trailing revision
+    /// (`-beta.1` -> `-beta.2`). Moving to a higher channel resets the
+    /// revision to 1 (`-beta.3` -> `-rc.1`). Moving to a lower channel than
+    /// the current one is rejected, since prerelease channels
+    /// only ever move forward towards a final release. Starting from a final
+    /// release bumps patch first and starts at revision 0 (`1.2.3` ->
+    /// `1.2.4-rc.0`), mirroring `bump_prerelease`.
+    pub fn bump_prerelease_channel(&mut self, channel: PrereleaseChannel) -> Result<&Version> {
+        let revision = match current_channel_and_revision(&self.current_version) {
+            Some((current_channel, current_revision)) => {
+                if channel < current_channel {
+                    anyhow::bail!(
+                        "cannot move from pre-release channel '{}' back to '{}'",
+                        current_channel.as_str(),
+                        channel.as_str()
+                    );
+                } else if channel == current_channel {
+                    current_revision + 1
+                } else {
+                    1
+                }
+            }
+            None => {
+                // Starting a pre-release from a final release begins at
+                // revision 0 (`1.2.3` -> `1.2.4-rc.0`), matching
+                // `bump_prerelease`'s starting point so the default
+                // `--pre-release-id rc` reaches it too.
+                self.current_version.patch += 1;
+                0
+            }
+        };
+
+        self.current_version.pre = Prerelease::new(&format!("{}.{}", channel.as_str(), revision))
+            .expect("channel and revision produce a valid semver identifier");
+        Ok(&self.current_version)
+    }
+
+    /// Strip the pre-release suffix, leaving `major.minor.patch` untouched.
+    /// Alias for `finalize_prerelease` with the name used by channel-based
+    /// callers.
+    pub fn promote_to_final(&mut self) -> &Version {
+        self.finalize_prerelease()
+    }
+
+    /// Attach `+<metadata>` build metadata to the current version.
+    pub fn set_build_metadata(&mut self, metadata: &str) -> Result<&Version> {
+        self.current_version.build = BuildMetadata::new(metadata)
+            .with_context(|| format!("'{}' is not valid semver build metadata", metadata))?;
+        Ok(&self.current_version)
+    }
+
+    /// Strip any `+<metadata>` build metadata from the current version.
+    pub fn clear_build_metadata(&mut self) -> &Version {
+        self.current_version.build = BuildMetadata::EMPTY;
+        &self.current_version
+    }
+
     pub fn detect_version_files(&mut self, project_root: &Path) -> Result<()> {
         let cargo_toml = project_root.join("Cargo.toml");
         let pyproject_toml = project_root.join("pyproject.toml");
@@ -47,96 +246,249 @@ impl VersionManager {
         let platformio_ini = project_root.join("platformio.ini");
         let library_json = project_root.join("library.json");
         let library_properties = project_root.join("library.properties");
+        let package_json = project_root.join("package.json");
+        let pom_xml = project_root.join("pom.xml");
+        let build_gradle = project_root.join("build.gradle");
+        let build_gradle_kts = project_root.join("build.gradle.kts");
+        let go_mod = project_root.join("go.mod");
+
+        // Files may disagree on the current version (e.g. a stale Cargo.toml
+        // next to an already-bumped package.json); `seen_version` tracks
+        // whether `current_version` has been set from a real file yet, so the
+        // first detected version always wins over the `0.1.0` default, and
+        // every later one only wins if it's actually higher (base version,
+        // then pre-release channel, then revision, per semver ordering).
+        let mut seen_version = false;
+
+        // Crate name -> Cargo.toml path, gathered while walking the root
+        // manifest and any workspace members, so locked versions in
+        // Cargo.lock can be cross-referenced against them afterwards.
+        let mut cargo_crates: Vec<(String, String)> = Vec::new();
 
         if cargo_toml.exists() {
+            let path = cargo_toml.to_string_lossy().into_owned();
             let content = fs::read_to_string(&cargo_toml)?;
             let doc = content.parse::<Document>()?;
-            if let Some(version) = doc["package"]["version"].as_str() {
-                self.current_version = Version::parse(version)?;
+            let workspace_version = doc
+                .get("workspace")
+                .and_then(|w| w.get("package"))
+                .and_then(|p| p.get("version"))
+                .and_then(|v| v.as_str())
+                .map(str::to_string);
+
+            if let Some(version) = resolve_cargo_package_version(&doc, workspace_version.as_deref()) {
+                self.adopt_version(&path, Version::parse(strip_v_prefix(&version))?, &mut seen_version);
+            }
+            if let Some(name) = doc.get("package").and_then(|p| p.get("name")).and_then(|n| n.as_str()) {
+                cargo_crates.push((name.to_string(), path.clone()));
+            }
+            self.project_files.push(ProjectFile::new(path, ProjectType::Rust));
+
+            if doc.get("workspace").is_some() {
+                self.detect_workspace_members(
+                    project_root,
+                    &doc,
+                    workspace_version.as_deref(),
+                    &mut seen_version,
+                    &mut cargo_crates,
+                )?;
+            }
+        }
+
+        let cargo_lock = project_root.join("Cargo.lock");
+        if cargo_lock.exists() && !cargo_crates.is_empty() {
+            let content = fs::read_to_string(&cargo_lock)?;
+            if let Ok(lock) = toml_edit::de::from_str::<CargoLock>(&content) {
+                for (name, _path) in &cargo_crates {
+                    if let Some(locked) = lock.packages.iter().find(|p| &p.name == name) {
+                        if let Ok(version) = Version::parse(strip_v_prefix(&locked.version)) {
+                            self.adopt_version(&format!("Cargo.lock ({name})"), version, &mut seen_version);
+                        }
+                    }
+                }
             }
-            self.project_files.push(ProjectFile {
-                path: cargo_toml.to_string_lossy().into_owned(),
-                project_type: ProjectType::Rust,
-            });
         }
 
         if pyproject_toml.exists() {
+            let path = pyproject_toml.to_string_lossy().into_owned();
             let content = fs::read_to_string(&pyproject_toml)?;
             let doc = content.parse::<Document>()?;
             if let Some(tool) = doc.get("tool") {
                 if let Some(Item::Table(poetry)) = tool.get("poetry") {
                     if let Some(version) = poetry["version"].as_str() {
-                        self.current_version = Version::parse(version)?;
+                        self.adopt_version(&path, Version::parse(strip_v_prefix(version))?, &mut seen_version);
                     }
                 }
             }
-            self.project_files.push(ProjectFile {
-                path: pyproject_toml.to_string_lossy().into_owned(),
-                project_type: ProjectType::Python,
-            });
+            self.project_files.push(ProjectFile::new(path, ProjectType::Python));
         }
 
         if cmake_lists.exists() {
+            let path = cmake_lists.to_string_lossy().into_owned();
             let content = fs::read_to_string(&cmake_lists)?;
             if let Some(version) = extract_cmake_version(&content)? {
-                self.current_version = version;
+                self.adopt_version(&path, version, &mut seen_version);
             }
-            self.project_files.push(ProjectFile {
-                path: cmake_lists.to_string_lossy().into_owned(),
-                project_type: ProjectType::Cpp,
-            });
+            self.project_files.push(ProjectFile::new(path, ProjectType::Cpp));
         }
 
         if meson_build.exists() {
+            let path = meson_build.to_string_lossy().into_owned();
             let content = fs::read_to_string(&meson_build)?;
             if let Some(version) = extract_meson_version(&content)? {
-                self.current_version = version;
+                self.adopt_version(&path, version, &mut seen_version);
             }
-            self.project_files.push(ProjectFile {
-                path: meson_build.to_string_lossy().into_owned(),
-                project_type: ProjectType::Meson,
-            });
+            self.project_files.push(ProjectFile::new(path, ProjectType::Meson));
         }
 
         // PlatformIO project detection
         if platformio_ini.exists() {
+            let path = platformio_ini.to_string_lossy().into_owned();
             let content = fs::read_to_string(&platformio_ini)?;
             if let Some(version) = extract_platformio_ini_version(&content)? {
-                self.current_version = version;
+                self.adopt_version(&path, version, &mut seen_version);
             }
-            self.project_files.push(ProjectFile {
-                path: platformio_ini.to_string_lossy().into_owned(),
-                project_type: ProjectType::PlatformIO,
-            });
+            self.project_files.push(ProjectFile::new(path, ProjectType::PlatformIO));
         }
 
         // PlatformIO library detection (library.json)
         if library_json.exists() {
+            let path = library_json.to_string_lossy().into_owned();
             let content = fs::read_to_string(&library_json)?;
             if let Some(version) = extract_library_json_version(&content)? {
-                self.current_version = version;
+                self.adopt_version(&path, version, &mut seen_version);
             }
-            self.project_files.push(ProjectFile {
-                path: library_json.to_string_lossy().into_owned(),
-                project_type: ProjectType::PlatformIO,
-            });
+            self.project_files.push(ProjectFile::new(path, ProjectType::PlatformIO));
         }
 
         // PlatformIO library detection (library.properties)
         if library_properties.exists() {
+            let path = library_properties.to_string_lossy().into_owned();
             let content = fs::read_to_string(&library_properties)?;
             if let Some(version) = extract_library_properties_version(&content)? {
-                self.current_version = version;
+                self.adopt_version(&path, version, &mut seen_version);
+            }
+            self.project_files.push(ProjectFile::new(path, ProjectType::PlatformIO));
+        }
+
+        // Node detection (package.json). Packages marked `"private": true`
+        // (e.g. an internal workspace root, as npm and starship both treat
+        // it) aren't published and don't carry a meaningful version, so skip
+        // tracking them entirely.
+        if package_json.exists() {
+            let path = package_json.to_string_lossy().into_owned();
+            let content = fs::read_to_string(&package_json)?;
+            if !is_private_package_json(&content)? {
+                if let Some(version) = extract_package_json_version(&content)? {
+                    self.adopt_version(&path, version, &mut seen_version);
+                }
+                self.project_files.push(ProjectFile::new(path, ProjectType::Node));
+            }
+        }
+
+        // Maven detection (pom.xml)
+        if pom_xml.exists() {
+            let path = pom_xml.to_string_lossy().into_owned();
+            let content = fs::read_to_string(&pom_xml)?;
+            if let Some(version) = extract_pom_xml_version(&content)? {
+                self.adopt_version(&path, version, &mut seen_version);
+            }
+            self.project_files.push(ProjectFile::new(path, ProjectType::Maven));
+        }
+
+        // Gradle detection (build.gradle / build.gradle.kts)
+        for gradle_file in [&build_gradle, &build_gradle_kts] {
+            if gradle_file.exists() {
+                let path = gradle_file.to_string_lossy().into_owned();
+                let content = fs::read_to_string(gradle_file)?;
+                if let Some(version) = extract_gradle_version(&content)? {
+                    self.adopt_version(&path, version, &mut seen_version);
+                }
+                self.project_files.push(ProjectFile::new(path, ProjectType::Gradle));
+            }
+        }
+
+        // Go module detection: the version lives in a git tag, not go.mod,
+        // so we register the file without touching `current_version`.
+        if go_mod.exists() {
+            self.project_files.push(ProjectFile::new(
+                go_mod.to_string_lossy().into_owned(),
+                ProjectType::Go,
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Expand a root `[workspace] members` glob (e.g. `crates/*`) into each
+    /// member's `Cargo.toml`, detecting its version the same way the root
+    /// manifest is detected, and registering it for write-back so
+    /// `update_all_versions` bumps the whole workspace in lockstep.
+    /// `version.workspace = true` members resolve from `workspace_version`
+    /// (the root's `[workspace.package].version`) instead of their own.
+    fn detect_workspace_members(
+        &mut self,
+        project_root: &Path,
+        root_doc: &Document,
+        workspace_version: Option<&str>,
+        seen_version: &mut bool,
+        cargo_crates: &mut Vec<(String, String)>,
+    ) -> Result<()> {
+        let Some(members) = root_doc["workspace"]["members"].as_array() else {
+            return Ok(());
+        };
+
+        for member_glob in members.iter().filter_map(|m| m.as_str()) {
+            for member_dir in expand_member_glob(project_root, member_glob)? {
+                let member_cargo_toml = member_dir.join("Cargo.toml");
+                if !member_cargo_toml.exists() {
+                    continue;
+                }
+
+                let path = member_cargo_toml.to_string_lossy().into_owned();
+                let content = fs::read_to_string(&member_cargo_toml)?;
+                let doc = content.parse::<Document>()?;
+
+                if let Some(version) = resolve_cargo_package_version(&doc, workspace_version) {
+                    self.adopt_version(&path, Version::parse(strip_v_prefix(&version))?, seen_version);
+                }
+                if let Some(name) = doc["package"]["name"].as_str() {
+                    cargo_crates.push((name.to_string(), path.clone()));
+                }
+                self.project_files.push(ProjectFile::new(path, ProjectType::Rust));
             }
-            self.project_files.push(ProjectFile {
-                path: library_properties.to_string_lossy().into_owned(),
-                project_type: ProjectType::PlatformIO,
-            });
         }
 
         Ok(())
     }
 
+    // Record `version` as detected for `path` (for `check_consistency`), and
+    // adopt it as `current_version` if it's the first detected version, or if
+    // it outranks the one already held (base version, then pre-release
+    // channel, then revision — `semver::Version`'s `Ord` implementation
+    // already orders a final release above any of its pre-releases and
+    // compares pre-release identifiers alphabetically, which matches
+    // `Alpha < Beta < Rc < Final`).
+    fn adopt_version(&mut self, path: &str, version: Version, seen_version: &mut bool) {
+        self.detected_versions.push((path.to_string(), version.clone()));
+        if !*seen_version || version > self.current_version {
+            self.current_version = version;
+        }
+        *seen_version = true;
+    }
+
+    /// Supplement the detected project files with any extra files declared
+    /// in `.bump.toml`, and apply any per-project-type `version_format`
+    /// override onto the files auto-detection already found.
+    pub fn apply_config(&mut self, config: &crate::config::Config) {
+        for project_file in &mut self.project_files {
+            if let Some(format) = config.version_format_for(project_file.project_type.config_key()) {
+                project_file.version_format = Some(format);
+            }
+        }
+        self.project_files.extend(config.configured_project_files());
+    }
+
     pub fn update_all_versions(&self, new_version: &str) -> Result<()> {
         for project_file in &self.project_files {
             project_file.update_version(new_version)?;
@@ -149,26 +501,128 @@ impl VersionManager {
     }
 }
 
+// Increment the trailing dot-separated identifier of a pre-release string.
+// Numeric tails increment numerically; non-numeric tails get a fresh ".0"
+// counter appended so the result stays a valid, orderable semver identifier.
+fn increment_prerelease_identifier(pre: &str) -> String {
+    match pre.rsplit_once('.') {
+        Some((prefix, tail)) => match tail.parse::<u64>() {
+            Ok(n) => format!("{}.{}", prefix, n + 1),
+            Err(_) => format!("{}.0", pre),
+        },
+        None => match pre.parse::<u64>() {
+            Ok(n) => (n + 1).to_string(),
+            Err(_) => format!("{}.0", pre),
+        },
+    }
+}
+
+// Parse a `Version`'s `pre` field as a channel/revision pair, e.g.
+// `Prerelease("beta.2")` -> `Some((PrereleaseChannel::Beta, 2))`. Returns
+// `None` for a final release or a pre-release label this crate doesn't
+// recognize as a channel.
+fn current_channel_and_revision(version: &Version) -> Option<(PrereleaseChannel, u64)> {
+    if version.pre.is_empty() {
+        return None;
+    }
+
+    let (label, revision) = version.pre.as_str().split_once('.')?;
+    let channel = PrereleaseChannel::parse(label)?;
+    let revision = revision.parse::<u64>().ok()?;
+    Some((channel, revision))
+}
+
+// Tolerate a leading `v`/`V`, so values written with a `v${raw}`-style
+// `version_format` (e.g. `v1.2.3`) still round-trip on the next detection.
+fn strip_v_prefix(version_str: &str) -> &str {
+    version_str
+        .strip_prefix(|c| c == 'v' || c == 'V')
+        .unwrap_or(version_str)
+}
+
+// Resolve a parsed Cargo.toml's effective `package.version`: its own string
+// value, or, when it opts into workspace inheritance via
+// `version.workspace = true`, the workspace's `[workspace.package].version`.
+fn resolve_cargo_package_version(doc: &Document, workspace_version: Option<&str>) -> Option<String> {
+    let version_item = doc.get("package").and_then(|p| p.get("version"))?;
+    if let Some(version) = version_item.as_str() {
+        return Some(version.to_string());
+    }
+
+    // `version.workspace = true` parses as an `Item::Table` (dotted key),
+    // while `version = { workspace = true }` parses as an inline table;
+    // `Item::get` reaches into either representation, so use that instead of
+    // `as_inline_table()`, which only matches the latter.
+    let inherits_workspace_version = version_item
+        .get("workspace")
+        .and_then(|workspace| workspace.as_bool())
+        .unwrap_or(false);
+
+    if inherits_workspace_version {
+        workspace_version.map(str::to_string)
+    } else {
+        None
+    }
+}
+
+// Expand a `[workspace] members` entry into its matching directories.
+// Supports a plain path and a single trailing `/*` glob segment (by far the
+// most common case in real workspaces), which covers most workspaces without
+// pulling in a dedicated glob dependency.
+fn expand_member_glob(project_root: &Path, pattern: &str) -> Result<Vec<PathBuf>> {
+    let Some(prefix) = pattern.strip_suffix("/*") else {
+        return Ok(vec![project_root.join(pattern)]);
+    };
+
+    let base = project_root.join(prefix);
+    if !base.is_dir() {
+        return Ok(Vec::new());
+    }
+
+    let mut members: Vec<PathBuf> = fs::read_dir(&base)
+        .with_context(|| format!("Failed to read {}", base.display()))?
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| path.is_dir())
+        .collect();
+    members.sort();
+    Ok(members)
+}
+
+// Mirrors just enough of tauri-cli's CargoLock/CargoLockPackage structs to
+// read each package's resolved version out of Cargo.lock.
+#[derive(Debug, Deserialize)]
+struct CargoLock {
+    #[serde(rename = "package")]
+    packages: Vec<CargoLockPackage>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CargoLockPackage {
+    name: String,
+    version: String,
+}
+
 // Helper function to extract version from CMakeLists.txt
 fn extract_cmake_version(content: &str) -> Result<Option<Version>> {
+    // Capture an optional `-prerelease+build` suffix alongside the numeric
+    // core so a suffixed version found in CMakeLists.txt (e.g. `1.2.4-rc.0`)
+    // is detected with its suffix. The CMake writer itself only writes the
+    // numeric core.
+    let suffix = r"((?:-[0-9A-Za-z.-]+)?(?:\+[0-9A-Za-z.-]+)?)";
+
     // Try to find version in project() call first
-    let project_re = Regex::new(r"project\s*\(\s*\w+\s+VERSION\s+(\d+)\.(\d+)\.(\d+)").unwrap();
+    let project_re = Regex::new(&format!(r"project\s*\(\s*\w+\s+VERSION\s+v?(\d+)\.(\d+)\.(\d+){suffix}")).unwrap();
     if let Some(caps) = project_re.captures(content) {
-        let major: u64 = caps.get(1).unwrap().as_str().parse()?;
-        let minor: u64 = caps.get(2).unwrap().as_str().parse()?;
-        let patch: u64 = caps.get(3).unwrap().as_str().parse()?;
-        return Ok(Some(Version::new(major, minor, patch)));
+        return parse_cmake_version_captures(&caps);
     }
-    
+
     // Try to find version in set(PROJECT_VERSION) call
-    let set_version_re = Regex::new(r"set\s*\(\s*(?:PROJECT|CMAKE_PROJECT)_VERSION\s+(\d+)\.(\d+)\.(\d+)").unwrap();
+    let set_version_re = Regex::new(&format!(r"set\s*\(\s*(?:PROJECT|CMAKE_PROJECT)_VERSION\s+v?(\d+)\.(\d+)\.(\d+){suffix}")).unwrap();
     if let Some(caps) = set_version_re.captures(content) {
-        let major: u64 = caps.get(1).unwrap().as_str().parse()?;
-        let minor: u64 = caps.get(2).unwrap().as_str().parse()?;
-        let patch: u64 = caps.get(3).unwrap().as_str().parse()?;
-        return Ok(Some(Version::new(major, minor, patch)));
+        return parse_cmake_version_captures(&caps);
     }
-    
+
     // Try to find individual version components
     let mut major: Option<u64> = None;
     let mut minor: Option<u64> = None;
@@ -199,14 +653,14 @@ fn extract_cmake_version(content: &str) -> Result<Option<Version>> {
 // Helper function to extract version from meson.build
 fn extract_meson_version(content: &str) -> Result<Option<Version>> {
     // Try to find version in project() call first
-    let project_re = Regex::new(r#"project\s*\(\s*['"][\w-]+['"](?:,\s*[^,)]+)*,\s*version\s*:\s*['"]([\d\.]+)['"]"#).unwrap();
+    let project_re = Regex::new(r#"project\s*\(\s*['"][\w-]+['"](?:,\s*[^,)]+)*,\s*version\s*:\s*['"]v?([\d.]+(?:-[0-9A-Za-z.-]+)?(?:\+[0-9A-Za-z.-]+)?)['"]"#).unwrap();
     if let Some(caps) = project_re.captures(content) {
         let version_str = caps.get(1).unwrap().as_str();
         return parse_version_string(version_str);
     }
-    
+
     // Try to find version variable declaration
-    let version_var_re = Regex::new(r#"(version|project_version)\s*=\s*['"]([\d\.]+)['"]"#).unwrap();
+    let version_var_re = Regex::new(r#"(version|project_version)\s*=\s*['"]v?([\d.]+(?:-[0-9A-Za-z.-]+)?(?:\+[0-9A-Za-z.-]+)?)['"]"#).unwrap();
     if let Some(caps) = version_var_re.captures(content) {
         let version_str = caps.get(2).unwrap().as_str();
         return parse_version_string(version_str);
@@ -239,13 +693,32 @@ fn extract_meson_version(content: &str) -> Result<Option<Version>> {
     Ok(None)
 }
 
-// Helper function to parse version string
+// Build a `Version` from a CMakeLists.txt regex match whose captures are
+// `(major, minor, patch, suffix)`, `suffix` being the optional
+// `-prerelease+build` tail.
+fn parse_cmake_version_captures(caps: &regex::Captures) -> Result<Option<Version>> {
+    let major = caps.get(1).unwrap().as_str();
+    let minor = caps.get(2).unwrap().as_str();
+    let patch = caps.get(3).unwrap().as_str();
+    let suffix = caps.get(4).map(|m| m.as_str()).unwrap_or("");
+    Ok(Version::parse(&format!("{major}.{minor}.{patch}{suffix}")).ok())
+}
+
+// Helper function to parse version string. Tries a full SemVer parse first
+// so a `-prerelease+build` suffix round-trips; falls back to reading just
+// the leading `major.minor.patch` components for formats that only ever
+// carry the bare numeric core.
 fn parse_version_string(version_str: &str) -> Result<Option<Version>> {
+    let version_str = strip_v_prefix(version_str);
+    if let Ok(version) = Version::parse(version_str) {
+        return Ok(Some(version));
+    }
+
     let parts: Vec<&str> = version_str.split('.').collect();
     if parts.len() < 3 {
         return Ok(None);
     }
-    
+
     let major: u64 = parts[0].parse()?;
     let minor: u64 = parts[1].parse()?;
     let patch: u64 = parts[2].parse()?;
@@ -255,7 +728,7 @@ fn parse_version_string(version_str: &str) -> Result<Option<Version>> {
 
 // Helper function to extract version from platformio.ini
 fn extract_platformio_ini_version(content: &str) -> Result<Option<Version>> {
-    let re = Regex::new(r#"version\s*=\s*["']?([\d\.]+)["']?"#).unwrap();
+    let re = Regex::new(r#"version\s*=\s*["']?v?([\d.]+(?:-[0-9A-Za-z.-]+)?(?:\+[0-9A-Za-z.-]+)?)["']?"#).unwrap();
     if let Some(caps) = re.captures(content) {
         let version_str = caps.get(1).unwrap().as_str();
         return parse_version_string(version_str);
@@ -276,9 +749,82 @@ fn extract_library_json_version(content: &str) -> Result<Option<Version>> {
     Ok(None)
 }
 
+// Helper function to extract version from package.json
+fn extract_package_json_version(content: &str) -> Result<Option<Version>> {
+    match serde_json::from_str::<serde_json::Value>(content) {
+        Ok(json) => {
+            if let Some(version_str) = json.get("version").and_then(|v| v.as_str()) {
+                if let Ok(version) = Version::parse(strip_v_prefix(version_str)) {
+                    return Ok(Some(version));
+                }
+            }
+        }
+        Err(_) => return Ok(None),
+    }
+    Ok(None)
+}
+
+// Helper function to check package.json's `"private": true` flag
+fn is_private_package_json(content: &str) -> Result<bool> {
+    match serde_json::from_str::<serde_json::Value>(content) {
+        Ok(json) => Ok(json.get("private").and_then(|v| v.as_bool()).unwrap_or(false)),
+        Err(_) => Ok(false),
+    }
+}
+
+// Helper function to extract the top-level <version> from pom.xml, ignoring
+// nested <version> tags inside <dependency>/<plugin> blocks. Uses an
+// event-based reader rather than regex so unrelated XML is never misread.
+fn extract_pom_xml_version(content: &str) -> Result<Option<Version>> {
+    use quick_xml::events::Event;
+    use quick_xml::reader::Reader;
+
+    let mut reader = Reader::from_str(content);
+    reader.config_mut().trim_text(true);
+
+    let mut path: Vec<String> = Vec::new();
+    let mut buf = Vec::new();
+
+    loop {
+        match reader.read_event_into(&mut buf)? {
+            Event::Start(e) => {
+                path.push(String::from_utf8_lossy(e.name().as_ref()).into_owned());
+                if path == ["project", "version"] {
+                    if let Event::Text(text) = reader.read_event_into(&mut buf)? {
+                        let version_str = text.unescape()?.into_owned();
+                        if let Ok(version) = Version::parse(strip_v_prefix(version_str.trim())) {
+                            return Ok(Some(version));
+                        }
+                    }
+                }
+            }
+            Event::End(_) => {
+                path.pop();
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+        buf.clear();
+    }
+
+    Ok(None)
+}
+
+// Helper function to extract version from build.gradle / build.gradle.kts
+fn extract_gradle_version(content: &str) -> Result<Option<Version>> {
+    let re = Regex::new(r#"version\s*=\s*["']([^"']+)["']"#).unwrap();
+    if let Some(caps) = re.captures(content) {
+        let version_str = caps.get(1).unwrap().as_str();
+        if let Ok(version) = Version::parse(strip_v_prefix(version_str)) {
+            return Ok(Some(version));
+        }
+    }
+    Ok(None)
+}
+
 // Helper function to extract version from library.properties
 fn extract_library_properties_version(content: &str) -> Result<Option<Version>> {
-    let re = Regex::new(r"version\s*=\s*([\d\.]+)").unwrap();
+    let re = Regex::new(r"version\s*=\s*v?([\d.]+(?:-[0-9A-Za-z.-]+)?(?:\+[0-9A-Za-z.-]+)?)").unwrap();
     if let Some(caps) = re.captures(content) {
         let version_str = caps.get(1).unwrap().as_str();
         return parse_version_string(version_str);